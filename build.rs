@@ -1,7 +1,17 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
 fn main() {
+    // Bindings generated into OUT_DIR are not inputs of the build.
+    println!("cargo:rerun-if-changed=wrapper.h");
+    println!("cargo:rerun-if-changed=src/bindings.rs");
+    println!("cargo:rerun-if-env-changed=DOCS_RS");
+    println!("cargo:rustc-check-cfg=cfg(libheif_security_limits)");
     if std::env::var("DOCS_RS").is_ok() {
         // Don't link with libheif in case of building documentation for docs.rs.
         println!("cargo:rustc-cfg=docs_rs");
+        generate_enums(&parse_bindings(Path::new("src/bindings.rs")));
         return;
     }
 
@@ -56,10 +66,12 @@ fn main() {
         }
     }
 
+    #[cfg(not(feature = "use-bindgen"))]
+    generate_enums(&parse_bindings(Path::new("src/bindings.rs")));
+
     #[cfg(feature = "use-bindgen")]
     {
         use std::env;
-        use std::path::PathBuf;
         // The bindgen::Builder is the main entry point
         // to bindgen, and lets you build up options for
         // the resulting bindings.
@@ -93,5 +105,208 @@ fn main() {
         bindings
             .write_to_file(out_path.join("bindings.rs"))
             .expect("Couldn't write bindings!");
+        let bindings = parse_bindings(&out_path.join("bindings.rs"));
+        // `heif_security_limits` API was added in libheif 1.19.
+        if has_struct(&bindings, "heif_security_limits") {
+            println!("cargo:rustc-cfg=libheif_security_limits");
+        }
+        generate_enums(&bindings);
+    }
+}
+
+/// Families of constants that have hand-written counterparts:
+/// enums with `Unknown` variant in "src/color_profile.rs" and
+/// `Brand` type in "src/sniff.rs" that replaces deprecated `heif_brand`.
+const SKIPPED_FAMILIES: &[&str] = &[
+    "heif_color_primaries",
+    "heif_transfer_characteristics",
+    "heif_matrix_coefficients",
+    "heif_brand",
+];
+
+const RENAMED_FAMILIES: &[(&str, &str)] = &[
+    ("heif_colorspace", "ColorSpace"),
+    ("heif_filetype_result", "FileTypeResult"),
+];
+
+struct Constant {
+    docs: Vec<String>,
+    name: String,
+    c_type: String,
+    value: String,
+}
+
+/// Bindings are parsed with `syn`, so they may be formatted by rustfmt
+/// or be written by bindgen into a single line.
+fn parse_bindings(bindings_path: &Path) -> syn::File {
+    let bindings = fs::read_to_string(bindings_path).expect("Couldn't read bindings!");
+    syn::parse_file(&bindings).expect("Couldn't parse bindings!")
+}
+
+#[cfg(feature = "use-bindgen")]
+fn has_struct(file: &syn::File, name: &str) -> bool {
+    file.items.iter().any(|item| match item {
+        syn::Item::Struct(item_struct) => item_struct.ident == name,
+        _ => false,
+    })
+}
+
+/// Generates "$OUT_DIR/enums.rs" with invocations of `c_enum!` macro
+/// for every family of `libc::c_uint` constants from given bindings.
+fn generate_enums(file: &syn::File) {
+    let mut families: Vec<String> = Vec::new();
+    let mut constants: Vec<Constant> = Vec::new();
+    for item in file.items.iter() {
+        match item {
+            syn::Item::Type(item_type) => {
+                let c_type = item_type.ident.to_string();
+                if c_type.starts_with("heif_")
+                    && type_name(&item_type.ty).as_deref() == Some("c_uint")
+                    && !SKIPPED_FAMILIES.contains(&c_type.as_str())
+                {
+                    families.push(c_type);
+                }
+            }
+            syn::Item::Const(item_const) => {
+                // Values of enum constants are always written as literals.
+                let value = match item_const.expr.as_ref() {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Int(value),
+                        ..
+                    }) => value.base10_digits().to_string(),
+                    _ => continue,
+                };
+                if let Some(c_type) = type_name(&item_const.ty) {
+                    constants.push(Constant {
+                        docs: docs(&item_const.attrs),
+                        name: item_const.ident.to_string(),
+                        c_type,
+                        value,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut res = String::from("// Generated by build.rs from bindings.rs, do not edit.\n");
+    for c_type in families.iter() {
+        let enum_name = enum_name(c_type);
+        let mut values: Vec<&str> = Vec::new();
+        let mut variants = String::new();
+        for constant in constants.iter().filter(|c| &c.c_type == c_type) {
+            // Aliases of known values can't be converted back from C value.
+            if values.contains(&constant.value.as_str()) {
+                continue;
+            }
+            values.push(&constant.value);
+            for doc in constant.docs.iter() {
+                writeln!(variants, "        #[doc = {:?}]", doc).unwrap();
+            }
+            let variant = variant_name(&enum_name, c_type, &constant.name);
+            writeln!(variants, "        {} = {},", variant, constant.name).unwrap();
+        }
+        if values.is_empty() {
+            continue;
+        }
+        writeln!(res, "\nc_enum! {{").unwrap();
+        writeln!(res, "    /// Values of `{}`.", c_type).unwrap();
+        writeln!(res, "    pub enum {}: {} {{", enum_name, c_type).unwrap();
+        res.push_str(&variants);
+        writeln!(res, "    }}\n}}").unwrap();
+    }
+
+    let out_path = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    fs::write(out_path.join("enums.rs"), res).expect("Couldn't write enums!");
+}
+
+/// The last segment of a path type, e.g. `c_uint` for `libc::c_uint`.
+fn type_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Values of `#[doc = "..."]` attributes.
+fn docs(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) => Some(doc.value()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `heif_chroma_downsampling_algorithm` -> `ChromaDownsamplingAlgorithm`
+fn enum_name(c_type: &str) -> String {
+    match RENAMED_FAMILIES.iter().find(|(name, _)| *name == c_type) {
+        Some((_, enum_name)) => enum_name.to_string(),
+        None => camel_case(c_type.trim_start_matches("heif_").split('_')),
+    }
+}
+
+/// `heif_chroma_heif_chroma_interleaved_RRGGBB_BE` -> `InterleavedRrggbbBe`
+///
+/// Names of constants repeat the name of family after the prefix
+/// `heif_`, sometimes in singular form. This part is removed.
+fn variant_name(enum_name: &str, c_type: &str, const_name: &str) -> String {
+    let rest = &const_name[c_type.len() + 1..];
+    let rest = rest.strip_prefix("heif_").unwrap_or(rest);
+    let words: Vec<&str> = rest.split('_').collect();
+    let family_words = c_type.trim_start_matches("heif_").split('_');
+    let mut skip = 0;
+    for family_word in family_words {
+        // At least one word must be left for the name of variant.
+        if skip + 1 >= words.len() {
+            break;
+        }
+        let word = words[skip];
+        if word != family_word && format!("{}s", word) != family_word {
+            break;
+        }
+        skip += 1;
+    }
+    let name = camel_case(words[skip..].iter().copied());
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        // `420` -> `C420`
+        format!("{}{}", &enum_name[..1], name)
+    } else {
+        name
+    }
+}
+
+/// Converts words into CamelCase. All-caps words like `HEVC`
+/// become `Hevc`, mixed-case words like `YCbCr` are kept as is.
+fn camel_case<'a>(words: impl Iterator<Item = &'a str>) -> String {
+    let mut res = String::new();
+    for word in words.filter(|w| !w.is_empty()) {
+        // Separate digits of adjacent words: `BT_709_5` -> `Bt709_5`.
+        let is_digit = |c: char| c.is_ascii_digit();
+        if res.ends_with(is_digit) && word.starts_with(is_digit) {
+            res.push('_');
+        }
+        let (first, rest) = word.split_at(1);
+        res.push_str(&first.to_ascii_uppercase());
+        // Only the rest of a word is checked, so `rICC` becomes `Ricc`.
+        if rest.contains(|c: char| c.is_ascii_lowercase()) {
+            res.push_str(rest);
+        } else {
+            res.push_str(&rest.to_ascii_lowercase());
+        }
     }
+    res
 }
@@ -0,0 +1,127 @@
+use std::ptr;
+
+use crate as lh;
+use crate::utils::{cstr_to_string, item_ids};
+use crate::{HeifError, ImageHandle, Result};
+
+// Values of macros `LIBHEIF_AUX_IMAGE_FILTER_*` which aren't emitted by bindgen.
+const AUX_IMAGE_FILTER_OMIT_ALPHA: libc::c_int = 1 << 1;
+const AUX_IMAGE_FILTER_OMIT_DEPTH: libc::c_int = 2 << 1;
+
+const APPLE_PHOTO_URN_PREFIX: &str = "urn:com:apple:photo:";
+
+/// Kind of auxiliary image detected by its type URN.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AuxiliaryKind {
+    Alpha,
+    Depth,
+    /// Apple HDR gain map.
+    HdrGainMap,
+    /// Apple portrait effects matte.
+    PortraitEffectsMatte,
+    /// Apple semantic segmentation matte with the name
+    /// of segmented object, e.g. `"skin"`, `"hair"` or `"teeth"`.
+    SemanticMatte(String),
+    Other(String),
+}
+
+impl AuxiliaryKind {
+    pub fn from_urn(urn: &str) -> Self {
+        match urn {
+            "urn:mpeg:hevc:2015:auxid:1" | "urn:mpeg:mpegB:cicp:systems:auxiliary:alpha" => {
+                Self::Alpha
+            }
+            "urn:mpeg:hevc:2015:auxid:2" | "urn:mpeg:mpegB:cicp:systems:auxiliary:depth" => {
+                Self::Depth
+            }
+            "urn:com:apple:photo:2020:aux:hdrgainmap" => Self::HdrGainMap,
+            "urn:com:apple:photo:2018:aux:portraiteffectsmatte" => Self::PortraitEffectsMatte,
+            _ => {
+                // E.g. "urn:com:apple:photo:2019:aux:semanticskinmatte"
+                let semantic_matte = urn
+                    .strip_prefix(APPLE_PHOTO_URN_PREFIX)
+                    .and_then(|rest| rest.split_once(":aux:semantic"))
+                    .map(|(_, name)| name.trim_end_matches("matte"));
+                match semantic_matte {
+                    Some(name) => Self::SemanticMatte(name.to_string()),
+                    None => Self::Other(urn.to_string()),
+                }
+            }
+        }
+    }
+}
+
+/// Auxiliary image attached to the main image.
+pub struct AuxiliaryImage {
+    pub handle: ImageHandle,
+    /// Type URN of the auxiliary image.
+    pub aux_type: String,
+    pub kind: AuxiliaryKind,
+}
+
+/// Iterator over auxiliary images of an image handle.
+pub struct AuxiliaryImages<'a> {
+    handle: &'a ImageHandle,
+    ids: std::vec::IntoIter<lh::heif_item_id>,
+}
+
+impl<'a> Iterator for AuxiliaryImages<'a> {
+    type Item = Result<AuxiliaryImage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.ids.next()?;
+        Some(self.handle.auxiliary_image(id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+impl ImageHandle {
+    /// Iterate over auxiliary images attached to the image.
+    ///
+    /// Alpha channel and depth images are usually accessed with help of
+    /// other methods, so they are skipped if `include_alpha_and_depth` is `false`.
+    pub fn auxiliary_images(&self, include_alpha_and_depth: bool) -> AuxiliaryImages<'_> {
+        let filter = if include_alpha_and_depth {
+            0
+        } else {
+            AUX_IMAGE_FILTER_OMIT_ALPHA | AUX_IMAGE_FILTER_OMIT_DEPTH
+        };
+        let count =
+            unsafe { lh::heif_image_handle_get_number_of_auxiliary_images(self.inner, filter) };
+        let ids = item_ids(count, |ids, count| unsafe {
+            lh::heif_image_handle_get_list_of_auxiliary_image_IDs(self.inner, filter, ids, count)
+        });
+        AuxiliaryImages {
+            handle: self,
+            ids: ids.into_iter(),
+        }
+    }
+
+    fn auxiliary_image(&self, auxiliary_id: lh::heif_item_id) -> Result<AuxiliaryImage> {
+        let mut c_handle = ptr::null_mut();
+        let err = unsafe {
+            lh::heif_image_handle_get_auxiliary_image_handle(
+                self.inner,
+                auxiliary_id,
+                &mut c_handle,
+            )
+        };
+        HeifError::from_heif_error(err)?;
+        let handle = ImageHandle { inner: c_handle };
+
+        let mut c_type = ptr::null();
+        let err = unsafe { lh::heif_image_handle_get_auxiliary_type(handle.inner, &mut c_type) };
+        HeifError::from_heif_error(err)?;
+        let aux_type = cstr_to_string(c_type).unwrap_or_default();
+        unsafe { lh::heif_image_handle_release_auxiliary_type(handle.inner, &mut c_type) };
+
+        Ok(AuxiliaryImage {
+            kind: AuxiliaryKind::from_urn(&aux_type),
+            handle,
+            aux_type,
+        })
+    }
+}
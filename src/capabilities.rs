@@ -0,0 +1,162 @@
+use std::fmt;
+
+use crate as lh;
+use crate::decoder::decoder_descriptors;
+use crate::utils::{cstr_to_string, ensure_initialized};
+use crate::{encoder_descriptors, CompressionFormat, Result};
+
+/// Report about version of `libheif` and its available codecs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capabilities {
+    /// Version of `libheif`, e.g. `"1.17.4"`.
+    pub version: String,
+    /// Version number in form of `0xHHMMLL00`.
+    pub version_number: u32,
+    /// Decoders sorted by format and priority.
+    /// A decoder supporting several formats is listed for each of them.
+    pub decoders: Vec<DecoderCapability>,
+    /// Encoders sorted by priority.
+    pub encoders: Vec<EncoderCapability>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecoderCapability {
+    pub name: String,
+    pub id_name: String,
+    pub format: CompressionFormat,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EncoderCapability {
+    pub name: String,
+    pub id_name: String,
+    /// `None` if the format isn't known by this version of the crate.
+    pub format: Option<CompressionFormat>,
+    pub lossy: bool,
+    pub lossless: bool,
+}
+
+impl Capabilities {
+    pub fn can_decode(&self, format: CompressionFormat) -> bool {
+        self.decoders.iter().any(|d| d.format == format)
+    }
+
+    pub fn can_encode(&self, format: CompressionFormat) -> bool {
+        self.encoders.iter().any(|e| e.format == Some(format))
+    }
+}
+
+/// Collect information about version of `libheif` and its codecs.
+pub fn capabilities() -> Result<Capabilities> {
+    ensure_initialized();
+    let mut decoders = Vec::new();
+    // Decoders are looked up for every known format,
+    // because decoder descriptors don't report their format.
+    let formats = CompressionFormat::ALL
+        .iter()
+        .filter(|&&format| format != CompressionFormat::Undefined);
+    for &format in formats {
+        decoders.extend(
+            decoder_descriptors(format)
+                .into_iter()
+                .map(|descriptor| unsafe {
+                    DecoderCapability {
+                        name: cstr_to_string(lh::heif_decoder_descriptor_get_name(descriptor))
+                            .unwrap_or_default(),
+                        id_name: cstr_to_string(lh::heif_decoder_descriptor_get_id_name(
+                            descriptor,
+                        ))
+                        .unwrap_or_default(),
+                        format,
+                    }
+                }),
+        );
+    }
+    let encoders = encoder_descriptors(None, None)?
+        .iter()
+        .map(|descriptor| EncoderCapability {
+            name: descriptor.name(),
+            id_name: descriptor.id_name(),
+            format: descriptor.compression_format(),
+            lossy: descriptor.supports_lossy_compression(),
+            lossless: descriptor.supports_lossless_compression(),
+        })
+        .collect();
+    Ok(Capabilities {
+        version: cstr_to_string(unsafe { lh::heif_get_version() }).unwrap_or_default(),
+        version_number: unsafe { lh::heif_get_version_number() },
+        decoders,
+        encoders,
+    })
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "libheif {}", self.version)?;
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+        let format_name = |format: Option<CompressionFormat>| match format {
+            Some(format) => format!("{:?}", format),
+            None => "unknown".to_string(),
+        };
+
+        writeln!(f, "Decoders:")?;
+        let rows: Vec<_> = self
+            .decoders
+            .iter()
+            .map(|d| {
+                vec![
+                    format_name(Some(d.format)),
+                    d.id_name.clone(),
+                    d.name.clone(),
+                ]
+            })
+            .collect();
+        write_table(f, &["format", "id", "name"], &rows)?;
+
+        writeln!(f, "Encoders:")?;
+        let rows: Vec<_> = self
+            .encoders
+            .iter()
+            .map(|e| {
+                vec![
+                    format_name(e.format),
+                    e.id_name.clone(),
+                    yes_no(e.lossy).to_string(),
+                    yes_no(e.lossless).to_string(),
+                    e.name.clone(),
+                ]
+            })
+            .collect();
+        write_table(f, &["format", "id", "lossy", "lossless", "name"], &rows)
+    }
+}
+
+/// Write rows as indented columns aligned by the widest value.
+fn write_table(f: &mut fmt::Formatter<'_>, header: &[&str], rows: &[Vec<String>]) -> fmt::Result {
+    if rows.is_empty() {
+        return writeln!(f, "  (none)");
+    }
+    let mut widths: Vec<_> = header.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+    let header: Vec<_> = header.iter().map(|h| h.to_string()).collect();
+    for row in std::iter::once(&header).chain(rows) {
+        let last = row.len() - 1;
+        write!(f, " ")?;
+        for (i, (value, width)) in row.iter().zip(&widths).enumerate() {
+            if i == last {
+                write!(f, " {}", value)?;
+            } else {
+                write!(f, " {:width$}", value, width = width)?;
+            }
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
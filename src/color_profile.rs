@@ -0,0 +1,270 @@
+use std::convert::TryFrom;
+use std::ptr;
+
+use crate as lh;
+use crate::{HeifError, Image, ImageHandle, Result};
+
+/// Color profile of an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorProfile {
+    /// Raw data of ICC profile. Restricted profiles are stored
+    /// in `colr` box with `rICC` type, other ones with `prof` type.
+    Icc {
+        restricted: bool,
+        data: Vec<u8>,
+    },
+    Nclx(NclxProfile),
+}
+
+c_enum_with_unknown! {
+    /// Color primaries defined by ITU-T H.273.
+    pub enum ColorPrimaries: heif_color_primaries {
+        Bt709 = heif_color_primaries_heif_color_primaries_ITU_R_BT_709_5,
+        Unspecified = heif_color_primaries_heif_color_primaries_unspecified,
+        Bt470SystemM = heif_color_primaries_heif_color_primaries_ITU_R_BT_470_6_System_M,
+        Bt470SystemBG = heif_color_primaries_heif_color_primaries_ITU_R_BT_470_6_System_B_G,
+        Bt601 = heif_color_primaries_heif_color_primaries_ITU_R_BT_601_6,
+        Smpte240M = heif_color_primaries_heif_color_primaries_SMPTE_240M,
+        GenericFilm = heif_color_primaries_heif_color_primaries_generic_film,
+        /// ITU-R BT.2020-2 and BT.2100-0
+        Bt2020 = heif_color_primaries_heif_color_primaries_ITU_R_BT_2020_2_and_2100_0,
+        SmpteSt428 = heif_color_primaries_heif_color_primaries_SMPTE_ST_428_1,
+        /// DCI-P3
+        SmpteRp431 = heif_color_primaries_heif_color_primaries_SMPTE_RP_431_2,
+        /// Display P3
+        SmpteEg432 = heif_color_primaries_heif_color_primaries_SMPTE_EG_432_1,
+        EbuTech3213E = heif_color_primaries_heif_color_primaries_EBU_Tech_3213_E,
+    }
+}
+
+c_enum_with_unknown! {
+    /// Transfer characteristics defined by ITU-T H.273.
+    pub enum TransferCharacteristics: heif_transfer_characteristics {
+        Bt709 = heif_transfer_characteristics_heif_transfer_characteristic_ITU_R_BT_709_5,
+        Unspecified = heif_transfer_characteristics_heif_transfer_characteristic_unspecified,
+        Bt470SystemM =
+            heif_transfer_characteristics_heif_transfer_characteristic_ITU_R_BT_470_6_System_M,
+        Bt470SystemBG =
+            heif_transfer_characteristics_heif_transfer_characteristic_ITU_R_BT_470_6_System_B_G,
+        Bt601 = heif_transfer_characteristics_heif_transfer_characteristic_ITU_R_BT_601_6,
+        Smpte240M = heif_transfer_characteristics_heif_transfer_characteristic_SMPTE_240M,
+        Linear = heif_transfer_characteristics_heif_transfer_characteristic_linear,
+        Logarithmic100 = heif_transfer_characteristics_heif_transfer_characteristic_logarithmic_100,
+        Logarithmic100Sqrt10 =
+            heif_transfer_characteristics_heif_transfer_characteristic_logarithmic_100_sqrt10,
+        /// xvYCC
+        Iec61966_2_4 = heif_transfer_characteristics_heif_transfer_characteristic_IEC_61966_2_4,
+        Bt1361 = heif_transfer_characteristics_heif_transfer_characteristic_ITU_R_BT_1361,
+        /// sRGB
+        Iec61966_2_1 = heif_transfer_characteristics_heif_transfer_characteristic_IEC_61966_2_1,
+        Bt2020_10Bit = heif_transfer_characteristics_heif_transfer_characteristic_ITU_R_BT_2020_2_10bit,
+        Bt2020_12Bit = heif_transfer_characteristics_heif_transfer_characteristic_ITU_R_BT_2020_2_12bit,
+        /// Perceptual quantization (SMPTE ST 2084)
+        Bt2100Pq = heif_transfer_characteristics_heif_transfer_characteristic_ITU_R_BT_2100_0_PQ,
+        SmpteSt428 = heif_transfer_characteristics_heif_transfer_characteristic_SMPTE_ST_428_1,
+        /// Hybrid log-gamma
+        Bt2100Hlg = heif_transfer_characteristics_heif_transfer_characteristic_ITU_R_BT_2100_0_HLG,
+    }
+}
+
+c_enum_with_unknown! {
+    /// Matrix coefficients defined by ITU-T H.273.
+    pub enum MatrixCoefficients: heif_matrix_coefficients {
+        RgbGbr = heif_matrix_coefficients_heif_matrix_coefficients_RGB_GBR,
+        Bt709 = heif_matrix_coefficients_heif_matrix_coefficients_ITU_R_BT_709_5,
+        Unspecified = heif_matrix_coefficients_heif_matrix_coefficients_unspecified,
+        UsFccT47 = heif_matrix_coefficients_heif_matrix_coefficients_US_FCC_T47,
+        Bt470SystemBG = heif_matrix_coefficients_heif_matrix_coefficients_ITU_R_BT_470_6_System_B_G,
+        Bt601 = heif_matrix_coefficients_heif_matrix_coefficients_ITU_R_BT_601_6,
+        Smpte240M = heif_matrix_coefficients_heif_matrix_coefficients_SMPTE_240M,
+        YCgCo = heif_matrix_coefficients_heif_matrix_coefficients_YCgCo,
+        Bt2020NonConstantLuminance =
+            heif_matrix_coefficients_heif_matrix_coefficients_ITU_R_BT_2020_2_non_constant_luminance,
+        Bt2020ConstantLuminance =
+            heif_matrix_coefficients_heif_matrix_coefficients_ITU_R_BT_2020_2_constant_luminance,
+        SmpteSt2085 = heif_matrix_coefficients_heif_matrix_coefficients_SMPTE_ST_2085,
+        ChromaticityDerivedNonConstantLuminance =
+            heif_matrix_coefficients_heif_matrix_coefficients_chromaticity_derived_non_constant_luminance,
+        ChromaticityDerivedConstantLuminance =
+            heif_matrix_coefficients_heif_matrix_coefficients_chromaticity_derived_constant_luminance,
+        ICtCp = heif_matrix_coefficients_heif_matrix_coefficients_ICtCp,
+    }
+}
+
+/// Color description of NCLX profile defined by ITU-T H.273.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NclxProfile {
+    pub color_primaries: ColorPrimaries,
+    pub transfer_characteristics: TransferCharacteristics,
+    pub matrix_coefficients: MatrixCoefficients,
+    /// Full range of values is used instead of the limited "studio" range.
+    pub full_range: bool,
+}
+
+impl NclxProfile {
+    pub fn new(
+        color_primaries: ColorPrimaries,
+        transfer_characteristics: TransferCharacteristics,
+        matrix_coefficients: MatrixCoefficients,
+        full_range: bool,
+    ) -> Self {
+        Self {
+            color_primaries,
+            transfer_characteristics,
+            matrix_coefficients,
+            full_range,
+        }
+    }
+}
+
+impl From<&lh::heif_color_profile_nclx> for NclxProfile {
+    fn from(profile: &lh::heif_color_profile_nclx) -> Self {
+        Self {
+            color_primaries: profile.color_primaries.into(),
+            transfer_characteristics: profile.transfer_characteristics.into(),
+            matrix_coefficients: profile.matrix_coefficients.into(),
+            full_range: profile.full_range_flag != 0,
+        }
+    }
+}
+
+/// Owner of `heif_color_profile_nclx` allocated by `libheif`.
+pub(crate) struct CNclxProfile {
+    pub(crate) inner: *mut lh::heif_color_profile_nclx,
+}
+
+impl CNclxProfile {
+    /// Allocates profile and fills it with help of `libheif` setters,
+    /// which return error for values unsupported by `libheif`.
+    pub(crate) fn new(profile: &NclxProfile) -> Result<Self> {
+        let inner = unsafe { lh::heif_nclx_color_profile_alloc() };
+        if inner.is_null() {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Memory_allocation_error,
+                lh::heif_suberror_code_heif_suberror_Unspecified,
+                "heif_nclx_color_profile_alloc() returned NULL",
+            ));
+        }
+        let res = Self { inner };
+        unsafe {
+            HeifError::from_heif_error(lh::heif_nclx_color_profile_set_color_primaries(
+                inner,
+                to_u16(profile.color_primaries.into())?,
+            ))?;
+            HeifError::from_heif_error(lh::heif_nclx_color_profile_set_transfer_characteristics(
+                inner,
+                to_u16(profile.transfer_characteristics.into())?,
+            ))?;
+            HeifError::from_heif_error(lh::heif_nclx_color_profile_set_matrix_coefficients(
+                inner,
+                to_u16(profile.matrix_coefficients.into())?,
+            ))?;
+            (*inner).full_range_flag = profile.full_range.into();
+        }
+        Ok(res)
+    }
+}
+
+fn to_u16(value: libc::c_uint) -> Result<u16> {
+    u16::try_from(value).map_err(|_| {
+        HeifError::new(
+            lh::heif_error_code_heif_error_Usage_error,
+            lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+            format!("NCLX value {} is too big", value),
+        )
+    })
+}
+
+impl Drop for CNclxProfile {
+    fn drop(&mut self) {
+        unsafe { lh::heif_nclx_color_profile_free(self.inner) };
+    }
+}
+
+/// Reads color profile with help of functions of image or image handle.
+fn read_color_profile(
+    profile_type: lh::heif_color_profile_type,
+    raw_size: impl FnOnce() -> usize,
+    read_raw: impl FnOnce(*mut libc::c_void) -> lh::heif_error,
+    read_nclx: impl FnOnce(*mut *mut lh::heif_color_profile_nclx) -> lh::heif_error,
+) -> Result<Option<ColorProfile>> {
+    match profile_type {
+        lh::heif_color_profile_type_heif_color_profile_type_prof
+        | lh::heif_color_profile_type_heif_color_profile_type_rICC => {
+            let mut data = vec![0u8; raw_size()];
+            HeifError::from_heif_error(read_raw(data.as_mut_ptr() as _))?;
+            Ok(Some(ColorProfile::Icc {
+                restricted: profile_type
+                    == lh::heif_color_profile_type_heif_color_profile_type_rICC,
+                data,
+            }))
+        }
+        lh::heif_color_profile_type_heif_color_profile_type_nclx => {
+            let mut c_profile = CNclxProfile {
+                inner: ptr::null_mut(),
+            };
+            HeifError::from_heif_error(read_nclx(&mut c_profile.inner))?;
+            if c_profile.inner.is_null() {
+                return Ok(None);
+            }
+            let profile = unsafe { &*c_profile.inner };
+            Ok(Some(ColorProfile::Nclx(profile.into())))
+        }
+        _ => Ok(None),
+    }
+}
+
+impl ImageHandle {
+    /// Color profile of the image. If the image has both ICC and NCLX
+    /// profiles, the ICC profile is returned.
+    pub fn color_profile(&self) -> Result<Option<ColorProfile>> {
+        let handle = self.inner;
+        unsafe {
+            read_color_profile(
+                lh::heif_image_handle_get_color_profile_type(handle),
+                || lh::heif_image_handle_get_raw_color_profile_size(handle),
+                |data| lh::heif_image_handle_get_raw_color_profile(handle, data),
+                |profile| lh::heif_image_handle_get_nclx_color_profile(handle, profile),
+            )
+        }
+    }
+}
+
+impl Image {
+    /// Color profile of the image. If the image has both ICC and NCLX
+    /// profiles, the ICC profile is returned.
+    pub fn color_profile(&self) -> Result<Option<ColorProfile>> {
+        let image = self.inner;
+        unsafe {
+            read_color_profile(
+                lh::heif_image_get_color_profile_type(image),
+                || lh::heif_image_get_raw_color_profile_size(image),
+                |data| lh::heif_image_get_raw_color_profile(image, data),
+                |profile| lh::heif_image_get_nclx_color_profile(image, profile),
+            )
+        }
+    }
+
+    /// Attach color profile to the image. Image may have both ICC and NCLX
+    /// profiles, setting of a profile replaces only the profile of the same kind.
+    pub fn set_color_profile(&mut self, profile: ColorProfile) -> Result<()> {
+        let err = match profile {
+            ColorProfile::Icc { restricted, data } => {
+                let profile_type: &[u8] = if restricted { b"rICC\0" } else { b"prof\0" };
+                unsafe {
+                    lh::heif_image_set_raw_color_profile(
+                        self.inner,
+                        profile_type.as_ptr() as _,
+                        data.as_ptr() as _,
+                        data.len(),
+                    )
+                }
+            }
+            ColorProfile::Nclx(nclx) => {
+                let c_profile = CNclxProfile::new(&nclx)?;
+                unsafe { lh::heif_image_set_nclx_color_profile(self.inner, c_profile.inner) }
+            }
+        };
+        HeifError::from_heif_error(err)
+    }
+}
@@ -0,0 +1,224 @@
+use std::path::Path;
+use std::ptr;
+use std::slice;
+
+use crate as lh;
+use crate::utils::{ensure_initialized, item_ids, str_to_cstring};
+use crate::{
+    CompressionFormat, Encoder, EncoderDescriptor, EncodingOptions, HeifError, Image, ImageHandle,
+    Result,
+};
+
+/// Safe owner of `heif_context`.
+pub struct HeifContext {
+    pub(crate) inner: *mut lh::heif_context,
+}
+
+impl HeifContext {
+    /// Create a new empty context.
+    pub fn new() -> Result<Self> {
+        ensure_initialized();
+        let inner = unsafe { lh::heif_context_alloc() };
+        if inner.is_null() {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Memory_allocation_error,
+                lh::heif_suberror_code_heif_suberror_Unspecified,
+                "heif_context_alloc() returned NULL",
+            ));
+        }
+        Ok(Self { inner })
+    }
+
+    /// Create a new context from bytes of HEIF file.
+    /// The data is copied into the context.
+    pub fn read_from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut ctx = Self::new()?;
+        ctx.read_bytes(bytes)?;
+        Ok(ctx)
+    }
+
+    pub(crate) fn read_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let err = unsafe {
+            lh::heif_context_read_from_memory(
+                self.inner,
+                bytes.as_ptr() as _,
+                bytes.len(),
+                ptr::null(),
+            )
+        };
+        HeifError::from_heif_error(err)
+    }
+
+    /// Create a new context from HEIF file.
+    pub fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let path_str = path.to_str().ok_or_else(|| {
+            HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Unspecified,
+                format!("path {:?} is not valid UTF-8", path),
+            )
+        })?;
+        let c_path = str_to_cstring(path_str)?;
+        let ctx = Self::new()?;
+        let err =
+            unsafe { lh::heif_context_read_from_file(ctx.inner, c_path.as_ptr(), ptr::null()) };
+        HeifError::from_heif_error(err)?;
+        Ok(ctx)
+    }
+
+    /// Raw pointer to the underlying `heif_context`.
+    pub fn as_ptr(&self) -> *mut lh::heif_context {
+        self.inner
+    }
+
+    pub fn primary_image_handle(&self) -> Result<ImageHandle> {
+        let mut c_handle = ptr::null_mut();
+        let err = unsafe { lh::heif_context_get_primary_image_handle(self.inner, &mut c_handle) };
+        HeifError::from_heif_error(err)?;
+        Ok(ImageHandle { inner: c_handle })
+    }
+
+    /// Iterate over top-level images of the file in order they are stored.
+    /// Thumbnails, auxiliary images and tiles of grid images are not included.
+    pub fn images(&self) -> TopLevelImages<'_> {
+        let count = unsafe { lh::heif_context_get_number_of_top_level_images(self.inner) };
+        let ids = item_ids(count, |ids, count| unsafe {
+            lh::heif_context_get_list_of_top_level_image_IDs(self.inner, ids, count)
+        });
+        TopLevelImages {
+            ctx: self,
+            ids: ids.into_iter(),
+        }
+    }
+
+    /// Get a handle of the top-level image with the given ID.
+    pub fn image_by_id(&self, id: lh::heif_item_id) -> Result<ImageHandle> {
+        if unsafe { lh::heif_context_is_top_level_image_ID(self.inner, id) } == 0 {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Nonexisting_item_referenced,
+                format!("item {} is not a top-level image", id),
+            ));
+        }
+        let mut c_handle = ptr::null_mut();
+        let err = unsafe { lh::heif_context_get_image_handle(self.inner, id, &mut c_handle) };
+        HeifError::from_heif_error(err)?;
+        Ok(ImageHandle { inner: c_handle })
+    }
+
+    /// Get an encoder with the highest priority for the given compression format.
+    pub fn encoder_for_format(&self, format: CompressionFormat) -> Result<Encoder> {
+        let mut c_encoder = ptr::null_mut();
+        let err = unsafe {
+            lh::heif_context_get_encoder_for_format(self.inner, format.into(), &mut c_encoder)
+        };
+        HeifError::from_heif_error(err)?;
+        Ok(Encoder { inner: c_encoder })
+    }
+
+    /// Get an encoder instance described by the given descriptor.
+    pub fn encoder(&self, descriptor: &EncoderDescriptor) -> Result<Encoder> {
+        let mut c_encoder = ptr::null_mut();
+        let err =
+            unsafe { lh::heif_context_get_encoder(self.inner, descriptor.inner, &mut c_encoder) };
+        HeifError::from_heif_error(err)?;
+        Ok(Encoder { inner: c_encoder })
+    }
+
+    /// Compress the image and add it into the context.
+    ///
+    /// The first image added to the context is also automatically set
+    /// as the primary image.
+    pub fn encode_image(
+        &mut self,
+        image: &Image,
+        encoder: &mut Encoder,
+        options: &EncodingOptions,
+    ) -> Result<ImageHandle> {
+        let mut c_handle = ptr::null_mut();
+        let err = unsafe {
+            lh::heif_context_encode_image(
+                self.inner,
+                image.inner,
+                encoder.inner,
+                options.as_ptr()?,
+                &mut c_handle,
+            )
+        };
+        HeifError::from_heif_error(err)?;
+        Ok(ImageHandle { inner: c_handle })
+    }
+
+    pub fn set_primary_image(&mut self, handle: &ImageHandle) -> Result<()> {
+        let err = unsafe { lh::heif_context_set_primary_image(self.inner, handle.inner) };
+        HeifError::from_heif_error(err)
+    }
+
+    /// Write the context content into a new vector of bytes.
+    pub fn write_to_bytes(&self) -> Result<Vec<u8>> {
+        let mut res = Vec::<u8>::new();
+        let mut writer = lh::heif_writer {
+            writer_api_version: 1,
+            write: Some(vector_writer),
+        };
+        let err = unsafe {
+            lh::heif_context_write(self.inner, &mut writer, &mut res as *mut Vec<u8> as _)
+        };
+        HeifError::from_heif_error(err)?;
+        Ok(res)
+    }
+}
+
+/// Top-level image of a file.
+pub struct TopLevelImage {
+    pub id: lh::heif_item_id,
+    pub is_primary: bool,
+    pub handle: ImageHandle,
+}
+
+/// Iterator over top-level images of a context.
+pub struct TopLevelImages<'a> {
+    ctx: &'a HeifContext,
+    ids: std::vec::IntoIter<lh::heif_item_id>,
+}
+
+impl<'a> Iterator for TopLevelImages<'a> {
+    type Item = Result<TopLevelImage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.ids.next()?;
+        Some(self.ctx.image_by_id(id).map(|handle| TopLevelImage {
+            id,
+            is_primary: handle.is_primary(),
+            handle,
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+unsafe extern "C" fn vector_writer(
+    _ctx: *mut lh::heif_context,
+    data: *const libc::c_void,
+    size: usize,
+    user_data: *mut libc::c_void,
+) -> lh::heif_error {
+    let res = &mut *(user_data as *mut Vec<u8>);
+    if size > 0 {
+        res.extend_from_slice(slice::from_raw_parts(data as *const u8, size));
+    }
+    lh::heif_error {
+        code: lh::heif_error_code_heif_error_Ok,
+        subcode: lh::heif_suberror_code_heif_suberror_Unspecified,
+        message: b"Success\0".as_ptr() as _,
+    }
+}
+
+impl Drop for HeifContext {
+    fn drop(&mut self) {
+        unsafe { lh::heif_context_free(self.inner) };
+    }
+}
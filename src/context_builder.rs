@@ -0,0 +1,90 @@
+use crate as lh;
+use crate::{CompressionFormat, EncodeSettings, HeifContext, HeifError, Image, Result};
+
+/// Image of a collection built by [`HeifContextBuilder`]
+/// with settings of its encoding.
+#[derive(Clone)]
+pub struct CollectionImage<'a> {
+    pub image: &'a Image,
+    pub format: CompressionFormat,
+    /// Settings of encoding and metadata attached to the image.
+    /// `bit_depth` is ignored, the image is encoded with bit depth
+    /// of its planes.
+    pub settings: EncodeSettings<'a>,
+    /// Size of square area into which the thumbnail of the image fits,
+    /// `None` if the image has no thumbnail.
+    pub thumbnail_bbox_size: Option<u32>,
+}
+
+impl<'a> CollectionImage<'a> {
+    /// Image encoded into HEVC with default settings.
+    pub fn new(image: &'a Image) -> Self {
+        Self {
+            image,
+            format: CompressionFormat::Hevc,
+            settings: EncodeSettings::default(),
+            thumbnail_bbox_size: None,
+        }
+    }
+}
+
+/// Builder of a context with a collection of top-level images,
+/// e.g. frames of a camera burst.
+#[derive(Default)]
+pub struct HeifContextBuilder<'a> {
+    images: Vec<CollectionImage<'a>>,
+    primary_index: usize,
+}
+
+impl<'a> HeifContextBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an image to the collection.
+    pub fn image(mut self, image: CollectionImage<'a>) -> Self {
+        self.images.push(image);
+        self
+    }
+
+    /// Index of the primary image in the collection, 0 by default.
+    pub fn primary(mut self, index: usize) -> Self {
+        self.primary_index = index;
+        self
+    }
+
+    /// Encode all images in order they were added into a new context.
+    pub fn build(&self) -> Result<HeifContext> {
+        if self.primary_index >= self.images.len() {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+                format!(
+                    "index {} of primary image is out of range, the collection has {} images",
+                    self.primary_index,
+                    self.images.len()
+                ),
+            ));
+        }
+
+        let mut ctx = HeifContext::new()?;
+        for (index, item) in self.images.iter().enumerate() {
+            let mut encoder = item.settings.encoder(&ctx, item.format)?;
+            let options = item.settings.encoding_options()?;
+            let handle = ctx.encode_image(item.image, &mut encoder, &options)?;
+            if index == self.primary_index {
+                ctx.set_primary_image(&handle)?;
+            }
+            if let Some(bbox_size) = item.thumbnail_bbox_size {
+                ctx.add_thumbnail(&handle, item.image, bbox_size, &mut encoder, &options)?;
+            }
+            item.settings.add_metadata(&mut ctx, &handle)?;
+        }
+        Ok(ctx)
+    }
+
+    /// Encode all images and write the result into a new vector of bytes.
+    pub fn write_to_bytes(&self) -> Result<Vec<u8>> {
+        self.build()?.write_to_bytes()
+    }
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use crate as lh;
+use crate::{Channel, Chroma, ColorSpace, HeifContext, HeifError, Image, Result};
+
+/// Packed interleaved pixels of a decoded image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedPixels<T> {
+    pub width: u32,
+    pub height: u32,
+    /// Number of bits used by every color component.
+    pub bit_depth: u8,
+    /// Rows of pixels without any padding between them.
+    pub pixels: Vec<T>,
+}
+
+/// Decode the primary image of HEIF file into 8-bit RGBA pixels.
+pub fn decode_to_rgba8(bytes: &[u8]) -> Result<DecodedPixels<u8>> {
+    let ctx = HeifContext::read_from_bytes(bytes)?;
+    decode_primary_rgba8(&ctx)
+}
+
+/// Decode the primary image of HEIF file into 8-bit RGBA pixels.
+pub fn decode_file_to_rgba8<P: AsRef<Path>>(path: P) -> Result<DecodedPixels<u8>> {
+    let ctx = HeifContext::read_from_file(path)?;
+    decode_primary_rgba8(&ctx)
+}
+
+/// Decode the primary image of HEIF file into RGB pixels with 16-bit components.
+///
+/// Components keep the bit depth of the source image,
+/// e.g. values of 10-bit image are in range 0-1023.
+pub fn decode_to_rgb16(bytes: &[u8]) -> Result<DecodedPixels<u16>> {
+    let ctx = HeifContext::read_from_bytes(bytes)?;
+    let handle = ctx.primary_image_handle()?;
+    let image = handle.decode(ColorSpace::Rgb, Chroma::InterleavedRrggbbLe)?;
+    let res = pack_interleaved(&image, 6)?;
+    Ok(DecodedPixels {
+        width: res.width,
+        height: res.height,
+        bit_depth: res.bit_depth,
+        pixels: res
+            .pixels
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect(),
+    })
+}
+
+fn decode_primary_rgba8(ctx: &HeifContext) -> Result<DecodedPixels<u8>> {
+    let handle = ctx.primary_image_handle()?;
+    let image = handle.decode(ColorSpace::Rgb, Chroma::InterleavedRgba)?;
+    pack_interleaved(&image, 4)
+}
+
+/// Copy rows of interleaved plane into a vector without padding.
+fn pack_interleaved(image: &Image, bytes_per_pixel: usize) -> Result<DecodedPixels<u8>> {
+    let plane = image.plane(Channel::Interleaved).ok_or_else(|| {
+        HeifError::new(
+            lh::heif_error_code_heif_error_Decoder_plugin_error,
+            lh::heif_suberror_code_heif_suberror_Nonexisting_image_channel_referenced,
+            "decoded image has no interleaved plane",
+        )
+    })?;
+    let row_size = plane.width as usize * bytes_per_pixel;
+    let mut pixels = Vec::with_capacity(row_size * plane.height as usize);
+    if row_size > 0 {
+        for row in plane.data.chunks(plane.stride).take(plane.height as usize) {
+            pixels.extend_from_slice(&row[..row_size]);
+        }
+    }
+    Ok(DecodedPixels {
+        width: plane.width,
+        height: plane.height,
+        bit_depth: plane.bits_per_pixel,
+        pixels,
+    })
+}
@@ -0,0 +1,77 @@
+use std::ffi::CString;
+use std::ptr;
+
+use crate as lh;
+use crate::utils::{cstr_to_string, ensure_initialized, str_to_cstring};
+use crate::{CompressionFormat, HeifError, Result};
+
+/// Decoder selected by its ID for decoding of images
+/// compressed with the given format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecoderChoice {
+    format: CompressionFormat,
+    id: CString,
+}
+
+impl DecoderChoice {
+    /// Choose a decoder by its ID, e.g. `"dav1d"` or `"aom"` for AV1.
+    /// Returns error if there is no such decoder for the format.
+    pub fn new(format: CompressionFormat, id: &str) -> Result<Self> {
+        ensure_initialized();
+        if unsafe { lh::heif_have_decoder_for_format(format.into()) } == 0 {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Unsupported_feature,
+                lh::heif_suberror_code_heif_suberror_Unsupported_codec,
+                format!("there is no decoder for {:?} format", format),
+            ));
+        }
+        if !Self::available_ids(format).iter().any(|i| i == id) {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+                format!("there is no decoder {:?} for {:?} format", id, format),
+            ));
+        }
+        Ok(Self {
+            format,
+            id: str_to_cstring(id)?,
+        })
+    }
+
+    /// IDs of decoders available for the format, sorted by their priority.
+    pub fn available_ids(format: CompressionFormat) -> Vec<String> {
+        ensure_initialized();
+        decoder_descriptors(format)
+            .into_iter()
+            .filter_map(|d| cstr_to_string(unsafe { lh::heif_decoder_descriptor_get_id_name(d) }))
+            .collect()
+    }
+
+    pub fn format(&self) -> CompressionFormat {
+        self.format
+    }
+
+    pub fn id(&self) -> &str {
+        // The ID was created from `&str`.
+        self.id.to_str().unwrap_or_default()
+    }
+
+    pub(crate) fn id_ptr(&self) -> *const libc::c_char {
+        self.id.as_ptr()
+    }
+}
+
+/// Get a list of decoders for the format sorted by their priority.
+pub(crate) fn decoder_descriptors(
+    format: CompressionFormat,
+) -> Vec<*const lh::heif_decoder_descriptor> {
+    let count = unsafe { lh::heif_get_decoder_descriptors(format.into(), ptr::null_mut(), 0) };
+    if count <= 0 {
+        return Vec::new();
+    }
+    let mut descriptors = vec![ptr::null(); count as usize];
+    let count =
+        unsafe { lh::heif_get_decoder_descriptors(format.into(), descriptors.as_mut_ptr(), count) };
+    descriptors.truncate(count.max(0) as usize);
+    descriptors
+}
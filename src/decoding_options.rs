@@ -0,0 +1,114 @@
+use crate as lh;
+use crate::{ColorConversionOptions, DecoderChoice, HeifError, Result};
+
+/// Builder of options used by decoding of images.
+///
+/// Owns the `heif_decoding_options` allocated by `libheif`
+/// and the decoder ID referenced from them.
+pub struct DecodingOptions {
+    inner: *mut lh::heif_decoding_options,
+    decoder: Option<DecoderChoice>,
+    /// The first error of setting of options, returned by `as_ptr()`.
+    error: Option<HeifError>,
+}
+
+impl DecodingOptions {
+    pub fn new() -> Result<Self> {
+        let inner = unsafe { lh::heif_decoding_options_alloc() };
+        if inner.is_null() {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Memory_allocation_error,
+                lh::heif_suberror_code_heif_suberror_Unspecified,
+                "heif_decoding_options_alloc() returned NULL",
+            ));
+        }
+        Ok(Self {
+            inner,
+            decoder: None,
+            error: None,
+        })
+    }
+
+    /// Ignore geometric transformations like cropping, rotation, mirroring.
+    /// Default: `false`.
+    pub fn ignore_transformations(mut self, enable: bool) -> Self {
+        if let Some(options) = self.options_mut(1, "ignore_transformations") {
+            options.ignore_transformations = enable.into();
+        }
+        self
+    }
+
+    /// Default: `false`.
+    pub fn convert_hdr_to_8bit(mut self, enable: bool) -> Self {
+        if let Some(options) = self.options_mut(2, "convert_hdr_to_8bit") {
+            options.convert_hdr_to_8bit = enable.into();
+        }
+        self
+    }
+
+    /// Return an error for invalid input instead of decoding it as good
+    /// as possible. Default: `false`.
+    pub fn strict_decoding(mut self, enable: bool) -> Self {
+        if let Some(options) = self.options_mut(3, "strict_decoding") {
+            options.strict_decoding = enable.into();
+        }
+        self
+    }
+
+    /// Decoder used instead of the one with the highest priority.
+    pub fn decoder(mut self, decoder: DecoderChoice) -> Self {
+        match self.options_mut(4, "decoder_id") {
+            Some(options) => options.decoder_id = decoder.id_ptr(),
+            None => return self,
+        }
+        self.decoder = Some(decoder);
+        self
+    }
+
+    pub fn color_conversion_options(mut self, conversion_options: ColorConversionOptions) -> Self {
+        if let Some(options) = self.options_mut(5, "color_conversion_options") {
+            options.color_conversion_options = conversion_options.into();
+        }
+        self
+    }
+
+    /// Returns options only if the allocated structure is new enough to
+    /// contain the field, otherwise remembers error about unsupported option.
+    fn options_mut(
+        &mut self,
+        min_version: u8,
+        option_name: &'static str,
+    ) -> Option<&mut lh::heif_decoding_options> {
+        let options = unsafe { &mut *self.inner };
+        if options.version >= min_version {
+            Some(options)
+        } else {
+            self.error.get_or_insert_with(|| {
+                HeifError::new(
+                    lh::heif_error_code_heif_error_Unsupported_feature,
+                    lh::heif_suberror_code_heif_suberror_Unsupported_parameter,
+                    format!(
+                        "decoding option '{}' is not supported by linked libheif",
+                        option_name
+                    ),
+                )
+            });
+            None
+        }
+    }
+
+    /// Pointer to options that may be passed into `libheif` functions.
+    /// Returns error if some of options isn't supported by linked version of `libheif`.
+    pub(crate) fn as_ptr(&self) -> Result<*const lh::heif_decoding_options> {
+        match &self.error {
+            Some(err) => Err(err.clone()),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+impl Drop for DecodingOptions {
+    fn drop(&mut self) {
+        unsafe { lh::heif_decoding_options_free(self.inner) };
+    }
+}
@@ -0,0 +1,205 @@
+use std::convert::TryFrom;
+use std::ptr;
+use std::slice;
+
+use crate as lh;
+use crate::enums::DepthRepresentationType;
+use crate::utils::item_ids;
+use crate::{HeifError, ImageHandle, Result};
+
+/// Depth image attached to the main image.
+pub struct DepthImage {
+    pub handle: ImageHandle,
+    /// Representation of depth values,
+    /// `None` if the file doesn't contain it.
+    pub info: Option<DepthRepresentation>,
+}
+
+/// Copy of `heif_depth_representation_info`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthRepresentation {
+    pub z_near: Option<f64>,
+    pub z_far: Option<f64>,
+    pub d_min: Option<f64>,
+    pub d_max: Option<f64>,
+    /// `None` if the type isn't known by this version of the crate.
+    pub representation_type: Option<DepthRepresentationType>,
+    pub disparity_reference_view: u32,
+    pub nonlinear_representation_model: Vec<u8>,
+}
+
+impl DepthRepresentation {
+    /// `info.depth_nonlinear_representation_model` must be NULL or point
+    /// to a buffer of `info.depth_nonlinear_representation_model_size` bytes.
+    unsafe fn from_c(info: &lh::heif_depth_representation_info) -> Self {
+        let value = |has_value: u8, value: f64| Some(value).filter(|_| has_value != 0);
+        let model = &info.depth_nonlinear_representation_model;
+        let model_size = info.depth_nonlinear_representation_model_size as usize;
+        Self {
+            z_near: value(info.has_z_near, info.z_near),
+            z_far: value(info.has_z_far, info.z_far),
+            d_min: value(info.has_d_min, info.d_min),
+            d_max: value(info.has_d_max, info.d_max),
+            representation_type: DepthRepresentationType::try_from(info.depth_representation_type)
+                .ok(),
+            disparity_reference_view: info.disparity_reference_view,
+            nonlinear_representation_model: if model.is_null() || model_size == 0 {
+                Vec::new()
+            } else {
+                slice::from_raw_parts(*model, model_size).to_vec()
+            },
+        }
+    }
+}
+
+/// Copies info returned by `libheif` and frees it with `free` function.
+unsafe fn take_representation_info(
+    has_info: libc::c_int,
+    c_info: *const lh::heif_depth_representation_info,
+    free: unsafe extern "C" fn(*const lh::heif_depth_representation_info),
+) -> Option<DepthRepresentation> {
+    if c_info.is_null() {
+        return None;
+    }
+    let info = Some(DepthRepresentation::from_c(&*c_info)).filter(|_| has_info != 0);
+    free(c_info);
+    info
+}
+
+/// Iterator over depth images of an image handle.
+pub struct DepthImages<'a> {
+    handle: &'a ImageHandle,
+    ids: std::vec::IntoIter<lh::heif_item_id>,
+}
+
+impl<'a> Iterator for DepthImages<'a> {
+    type Item = Result<DepthImage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.ids.next()?;
+        Some(self.handle.depth_image(id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+impl ImageHandle {
+    pub fn has_depth_image(&self) -> bool {
+        unsafe { lh::heif_image_handle_has_depth_image(self.inner) != 0 }
+    }
+
+    /// Iterate over depth images attached to the image.
+    pub fn depth_images(&self) -> DepthImages<'_> {
+        let count = unsafe { lh::heif_image_handle_get_number_of_depth_images(self.inner) };
+        let ids = item_ids(count, |ids, count| unsafe {
+            lh::heif_image_handle_get_list_of_depth_image_IDs(self.inner, ids, count)
+        });
+        DepthImages {
+            handle: self,
+            ids: ids.into_iter(),
+        }
+    }
+
+    fn depth_image(&self, depth_image_id: lh::heif_item_id) -> Result<DepthImage> {
+        let mut c_handle = ptr::null_mut();
+        let err = unsafe {
+            lh::heif_image_handle_get_depth_image_handle(self.inner, depth_image_id, &mut c_handle)
+        };
+        HeifError::from_heif_error(err)?;
+        let handle = ImageHandle { inner: c_handle };
+        let info = handle.depth_representation_info(depth_image_id);
+        Ok(DepthImage { handle, info })
+    }
+
+    /// Representation info is stored in properties of the depth image,
+    /// so it must be requested from handle of the depth image itself
+    /// rather than from handle of its master image.
+    fn depth_representation_info(
+        &self,
+        depth_image_id: lh::heif_item_id,
+    ) -> Option<DepthRepresentation> {
+        let mut c_info = ptr::null();
+        unsafe {
+            let has_info = lh::heif_image_handle_get_depth_image_representation_info(
+                self.inner,
+                depth_image_id,
+                &mut c_info,
+            );
+            take_representation_info(has_info, c_info, lh::heif_depth_representation_info_free)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    thread_local! {
+        static FREED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe extern "C" fn fake_free(_info: *const lh::heif_depth_representation_info) {
+        FREED.with(|freed| freed.set(freed.get() + 1));
+    }
+
+    #[test]
+    fn copy_depth_representation_info() {
+        let mut model = vec![1u8, 2, 3];
+        let info = lh::heif_depth_representation_info {
+            version: 1,
+            has_z_near: 1,
+            has_z_far: 0,
+            has_d_min: 1,
+            has_d_max: 0,
+            z_near: 0.5,
+            z_far: 100.0,
+            d_min: -1.0,
+            d_max: 1.0,
+            depth_representation_type:
+                lh::heif_depth_representation_type_heif_depth_representation_type_uniform_disparity,
+            disparity_reference_view: 7,
+            depth_nonlinear_representation_model_size: model.len() as u32,
+            depth_nonlinear_representation_model: model.as_mut_ptr(),
+        };
+        let expected = DepthRepresentation {
+            z_near: Some(0.5),
+            z_far: None,
+            d_min: Some(-1.0),
+            d_max: None,
+            representation_type: Some(DepthRepresentationType::UniformDisparity),
+            disparity_reference_view: 7,
+            nonlinear_representation_model: vec![1, 2, 3],
+        };
+        let res = unsafe { take_representation_info(1, &info, fake_free) };
+        assert_eq!(res.as_ref(), Some(&expected));
+        assert_eq!(FREED.with(Cell::get), 1);
+
+        let info = lh::heif_depth_representation_info {
+            has_z_far: 1,
+            depth_representation_type: 100,
+            depth_nonlinear_representation_model: ptr::null_mut(),
+            ..info
+        };
+        let res = unsafe { take_representation_info(1, &info, fake_free) }.unwrap();
+        assert_eq!(res.z_far, Some(100.0));
+        assert_eq!(res.representation_type, None);
+        assert!(res.nonlinear_representation_model.is_empty());
+        assert_eq!(FREED.with(Cell::get), 2);
+
+        // Info is freed even if `libheif` reports that it is missing.
+        assert_eq!(
+            unsafe { take_representation_info(0, &info, fake_free) },
+            None
+        );
+        assert_eq!(FREED.with(Cell::get), 3);
+        assert_eq!(
+            unsafe { take_representation_info(1, ptr::null(), fake_free) },
+            None
+        );
+        assert_eq!(FREED.with(Cell::get), 3);
+    }
+}
@@ -0,0 +1,194 @@
+use crate as lh;
+use crate::{
+    Channel, Chroma, ColorSpace, CompressionFormat, Encoder, EncodingOptions, HeifContext,
+    HeifError, Image, ImageHandle, MetadataCompression, Result,
+};
+
+/// Layout of interleaved pixels passed into encoding helpers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PixelLayout {
+    Rgb,
+    Rgba,
+}
+
+impl PixelLayout {
+    fn channels(self) -> usize {
+        match self {
+            Self::Rgb => 3,
+            Self::Rgba => 4,
+        }
+    }
+
+    fn chroma(self, bit_depth: u8) -> Chroma {
+        match (self, bit_depth > 8) {
+            (Self::Rgb, false) => Chroma::InterleavedRgb,
+            (Self::Rgba, false) => Chroma::InterleavedRgba,
+            (Self::Rgb, true) => Chroma::InterleavedRrggbbLe,
+            (Self::Rgba, true) => Chroma::InterleavedRrggbbaaLe,
+        }
+    }
+}
+
+/// Settings of one-call encoding helpers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeSettings<'a> {
+    /// Quality factor (0-100). It is ignored in lossless mode.
+    pub quality: u8,
+    pub lossless: bool,
+    /// Number of bits per color component: 8, 10 or 12.
+    /// Components with bit depth bigger than 8 are stored in pixels
+    /// as two bytes in little-endian order.
+    pub bit_depth: u8,
+    /// Save alpha channel of RGBA pixels into the result image.
+    pub save_alpha: bool,
+    /// EXIF metadata attached to the result image, raw TIFF data
+    /// or payload of JPEG APP1 segment starting with `Exif\0\0`.
+    pub exif: Option<&'a [u8]>,
+    /// XMP metadata attached to the result image.
+    pub xmp: Option<&'a str>,
+}
+
+impl Default for EncodeSettings<'_> {
+    fn default() -> Self {
+        Self {
+            quality: 80,
+            lossless: false,
+            bit_depth: 8,
+            save_alpha: true,
+            exif: None,
+            xmp: None,
+        }
+    }
+}
+
+impl EncodeSettings<'_> {
+    pub(crate) fn encoder(&self, ctx: &HeifContext, format: CompressionFormat) -> Result<Encoder> {
+        let mut encoder = ctx.encoder_for_format(format)?;
+        encoder.set_lossless(self.lossless)?;
+        if !self.lossless {
+            encoder.set_quality(self.quality)?;
+        }
+        Ok(encoder)
+    }
+
+    pub(crate) fn encoding_options(&self) -> Result<EncodingOptions> {
+        Ok(EncodingOptions::new()?.save_alpha_channel(self.save_alpha))
+    }
+
+    pub(crate) fn add_metadata(&self, ctx: &mut HeifContext, handle: &ImageHandle) -> Result<()> {
+        if let Some(exif) = self.exif {
+            ctx.add_exif(handle, exif)?;
+        }
+        if let Some(xmp) = self.xmp {
+            ctx.add_xmp(handle, xmp, MetadataCompression::Off)?;
+        }
+        Ok(())
+    }
+}
+
+/// Encode interleaved RGBA pixels into HEIC file.
+pub fn encode_rgba_to_heic(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    settings: &EncodeSettings,
+) -> Result<Vec<u8>> {
+    encode_interleaved(
+        CompressionFormat::Hevc,
+        PixelLayout::Rgba,
+        pixels,
+        width,
+        height,
+        settings,
+    )
+}
+
+/// Encode interleaved RGB pixels into AVIF file.
+pub fn encode_rgb_to_avif(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    settings: &EncodeSettings,
+) -> Result<Vec<u8>> {
+    encode_interleaved(
+        CompressionFormat::Av1,
+        PixelLayout::Rgb,
+        pixels,
+        width,
+        height,
+        settings,
+    )
+}
+
+/// Encode interleaved pixels into a file with a single image
+/// compressed with the given format.
+pub fn encode_interleaved(
+    format: CompressionFormat,
+    layout: PixelLayout,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    settings: &EncodeSettings,
+) -> Result<Vec<u8>> {
+    let image = create_interleaved_image(layout, pixels, width, height, settings.bit_depth)?;
+
+    let mut ctx = HeifContext::new()?;
+    let mut encoder = settings.encoder(&ctx, format)?;
+    let options = settings.encoding_options()?;
+    let handle = ctx.encode_image(&image, &mut encoder, &options)?;
+    ctx.set_primary_image(&handle)?;
+    settings.add_metadata(&mut ctx, &handle)?;
+    ctx.write_to_bytes()
+}
+
+fn create_interleaved_image(
+    layout: PixelLayout,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    bit_depth: u8,
+) -> Result<Image> {
+    if !matches!(bit_depth, 8 | 10 | 12) {
+        return Err(invalid_parameter(format!(
+            "unsupported bit depth {}",
+            bit_depth
+        )));
+    }
+    let bytes_per_component = if bit_depth > 8 { 2 } else { 1 };
+    let row_size = width as usize * layout.channels() * bytes_per_component;
+    if pixels.len() != row_size * height as usize {
+        return Err(invalid_parameter(format!(
+            "size of pixels buffer must be {} bytes, got {}",
+            row_size * height as usize,
+            pixels.len()
+        )));
+    }
+
+    let mut image = Image::new(width, height, ColorSpace::Rgb, layout.chroma(bit_depth))?;
+    image.add_plane(Channel::Interleaved, width, height, bit_depth)?;
+    let plane = image.plane_mut(Channel::Interleaved).ok_or_else(|| {
+        HeifError::new(
+            lh::heif_error_code_heif_error_Memory_allocation_error,
+            lh::heif_suberror_code_heif_suberror_Unspecified,
+            "interleaved plane was not created",
+        )
+    })?;
+    if row_size > 0 {
+        for (dst_row, src_row) in plane
+            .data
+            .chunks_mut(plane.stride)
+            .zip(pixels.chunks_exact(row_size))
+        {
+            dst_row[..row_size].copy_from_slice(src_row);
+        }
+    }
+    Ok(image)
+}
+
+fn invalid_parameter(message: String) -> HeifError {
+    HeifError::new(
+        lh::heif_error_code_heif_error_Usage_error,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+        message,
+    )
+}
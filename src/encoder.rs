@@ -0,0 +1,130 @@
+use std::convert::TryFrom;
+use std::ptr;
+
+use crate as lh;
+use crate::enums::CompressionFormat;
+use crate::utils::{cstr_to_string, ensure_initialized, str_to_cstring};
+use crate::{HeifError, Result};
+
+/// Description of an encoder registered in `libheif`.
+#[derive(Debug, Copy, Clone)]
+pub struct EncoderDescriptor {
+    pub(crate) inner: *const lh::heif_encoder_descriptor,
+}
+
+impl EncoderDescriptor {
+    /// A long, descriptive name of the encoder (including version information).
+    pub fn name(&self) -> String {
+        cstr_to_string(unsafe { lh::heif_encoder_descriptor_get_name(self.inner) })
+            .unwrap_or_default()
+    }
+
+    /// A short, symbolic name for identifying the encoder.
+    pub fn id_name(&self) -> String {
+        cstr_to_string(unsafe { lh::heif_encoder_descriptor_get_id_name(self.inner) })
+            .unwrap_or_default()
+    }
+
+    pub fn compression_format(&self) -> Option<CompressionFormat> {
+        let format = unsafe { lh::heif_encoder_descriptor_get_compression_format(self.inner) };
+        CompressionFormat::try_from(format).ok()
+    }
+
+    pub fn supports_lossy_compression(&self) -> bool {
+        unsafe { lh::heif_encoder_descriptor_supports_lossy_compression(self.inner) != 0 }
+    }
+
+    pub fn supports_lossless_compression(&self) -> bool {
+        unsafe { lh::heif_encoder_descriptor_supports_lossless_compression(self.inner) != 0 }
+    }
+}
+
+/// Get a list of available encoders sorted by their priority.
+/// Encoders may be filtered by compression format and by name.
+pub fn encoder_descriptors(
+    format: Option<CompressionFormat>,
+    name_filter: Option<&str>,
+) -> Result<Vec<EncoderDescriptor>> {
+    ensure_initialized();
+    let format = format.unwrap_or(CompressionFormat::Undefined).into();
+    let c_name_filter = name_filter.map(str_to_cstring).transpose()?;
+    let name_ptr = c_name_filter.as_ref().map_or(ptr::null(), |s| s.as_ptr());
+    // Old versions of libheif can't return the total number of encoders
+    // without an output array, so grow the array until it is big enough.
+    let mut descriptors = vec![ptr::null(); 16];
+    loop {
+        let count = unsafe {
+            lh::heif_get_encoder_descriptors(
+                format,
+                name_ptr,
+                descriptors.as_mut_ptr(),
+                descriptors.len() as _,
+            )
+        };
+        let count = count.max(0) as usize;
+        if count < descriptors.len() {
+            descriptors.truncate(count);
+            break;
+        }
+        descriptors.resize(descriptors.len() * 2, ptr::null());
+    }
+    Ok(descriptors
+        .into_iter()
+        .map(|inner| EncoderDescriptor { inner })
+        .collect())
+}
+
+/// Safe owner of `heif_encoder`. The encoder is released on drop.
+pub struct Encoder {
+    pub(crate) inner: *mut lh::heif_encoder,
+}
+
+impl Encoder {
+    /// Name of encoder.
+    pub fn name(&self) -> String {
+        cstr_to_string(unsafe { lh::heif_encoder_get_name(self.inner) }).unwrap_or_default()
+    }
+
+    /// Set a 'quality' factor (0-100). How this is mapped to actual
+    /// encoding parameters is encoder dependent.
+    pub fn set_quality(&mut self, quality: u8) -> Result<()> {
+        if quality > 100 {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+                format!("quality must be in range 0-100, got {}", quality),
+            ));
+        }
+        let err = unsafe { lh::heif_encoder_set_lossy_quality(self.inner, quality.into()) };
+        HeifError::from_heif_error(err)
+    }
+
+    pub fn set_lossless(&mut self, enable: bool) -> Result<()> {
+        let err = unsafe { lh::heif_encoder_set_lossless(self.inner, enable.into()) };
+        HeifError::from_heif_error(err)
+    }
+
+    /// Level should be between 0 (= none) to 4 (= full).
+    pub fn set_logging_level(&mut self, level: u8) -> Result<()> {
+        if level > 4 {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+                format!("logging level must be in range 0-4, got {}", level),
+            ));
+        }
+        let err = unsafe { lh::heif_encoder_set_logging_level(self.inner, level.into()) };
+        HeifError::from_heif_error(err)
+    }
+
+    /// Raw pointer to the underlying `heif_encoder`.
+    pub fn as_ptr(&self) -> *mut lh::heif_encoder {
+        self.inner
+    }
+}
+
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        unsafe { lh::heif_encoder_release(self.inner) };
+    }
+}
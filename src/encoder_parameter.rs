@@ -0,0 +1,284 @@
+use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::ptr;
+use std::slice;
+
+use crate as lh;
+use crate::utils::{cstr_to_string, str_to_cstring};
+use crate::{Encoder, HeifError, Result};
+
+const MAX_STRING_VALUE_SIZE: usize = 64 * 1024;
+
+/// Type of encoder's parameter and its valid values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncoderParameterKind {
+    Integer {
+        min: Option<i32>,
+        max: Option<i32>,
+        /// Fixed set of valid values. Empty if values are limited by range only.
+        valid_values: Vec<i32>,
+    },
+    Boolean,
+    String {
+        /// Fixed set of valid values. Empty if all values are allowed.
+        valid_values: Vec<String>,
+    },
+}
+
+/// Description of a configuration parameter of an encoder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncoderParameter {
+    pub name: String,
+    pub kind: EncoderParameterKind,
+    pub has_default: bool,
+}
+
+/// Value of encoder's parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParamValue {
+    Integer(i32),
+    Boolean(bool),
+    String(String),
+}
+
+impl EncoderParameter {
+    /// Check that the given value has the parameter's type
+    /// and lies in the range of its valid values.
+    pub fn validate(&self, value: &ParamValue) -> Result<()> {
+        let is_valid = match (&self.kind, value) {
+            (
+                EncoderParameterKind::Integer {
+                    min,
+                    max,
+                    valid_values,
+                },
+                ParamValue::Integer(v),
+            ) => {
+                !matches!(min, Some(min) if v < min)
+                    && !matches!(max, Some(max) if v > max)
+                    && (valid_values.is_empty() || valid_values.contains(v))
+            }
+            (EncoderParameterKind::Boolean, ParamValue::Boolean(_)) => true,
+            (EncoderParameterKind::String { valid_values }, ParamValue::String(v)) => {
+                valid_values.is_empty() || valid_values.contains(v)
+            }
+            _ => false,
+        };
+        if is_valid {
+            Ok(())
+        } else {
+            Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+                format!("invalid value {:?} for parameter '{}'", value, self.name),
+            ))
+        }
+    }
+}
+
+/// Iterator over parameters of an encoder.
+pub struct EncoderParameters<'a> {
+    encoder: *mut lh::heif_encoder,
+    next: *const *const lh::heif_encoder_parameter,
+    phantom: PhantomData<&'a Encoder>,
+}
+
+impl<'a> Iterator for EncoderParameters<'a> {
+    type Item = Result<EncoderParameter>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.is_null() {
+            return None;
+        }
+        let c_param = unsafe { *self.next };
+        if c_param.is_null() {
+            return None;
+        }
+        self.next = unsafe { self.next.add(1) };
+        Some(unsafe { read_parameter(self.encoder, c_param) })
+    }
+}
+
+unsafe fn read_parameter(
+    encoder: *mut lh::heif_encoder,
+    c_param: *const lh::heif_encoder_parameter,
+) -> Result<EncoderParameter> {
+    let c_name = lh::heif_encoder_parameter_get_name(c_param);
+    let name = cstr_to_string(c_name).unwrap_or_default();
+    let kind = match lh::heif_encoder_parameter_get_type(c_param) {
+        lh::heif_encoder_parameter_type_heif_encoder_parameter_type_integer => {
+            let mut have_min = 0;
+            let mut have_max = 0;
+            let mut min = 0;
+            let mut max = 0;
+            let mut num_values = 0;
+            let mut values_ptr = ptr::null();
+            let err = lh::heif_encoder_parameter_get_valid_integer_values(
+                c_param,
+                &mut have_min,
+                &mut have_max,
+                &mut min,
+                &mut max,
+                &mut num_values,
+                &mut values_ptr,
+            );
+            HeifError::from_heif_error(err)?;
+            let valid_values = if num_values > 0 && !values_ptr.is_null() {
+                slice::from_raw_parts(values_ptr, num_values as usize).to_vec()
+            } else {
+                Vec::new()
+            };
+            EncoderParameterKind::Integer {
+                min: if have_min != 0 { Some(min) } else { None },
+                max: if have_max != 0 { Some(max) } else { None },
+                valid_values,
+            }
+        }
+        lh::heif_encoder_parameter_type_heif_encoder_parameter_type_boolean => {
+            EncoderParameterKind::Boolean
+        }
+        lh::heif_encoder_parameter_type_heif_encoder_parameter_type_string => {
+            let mut values_ptr = ptr::null();
+            let err = lh::heif_encoder_parameter_get_valid_string_values(c_param, &mut values_ptr);
+            HeifError::from_heif_error(err)?;
+            let mut valid_values = Vec::new();
+            if !values_ptr.is_null() {
+                while !(*values_ptr).is_null() {
+                    valid_values.push(CStr::from_ptr(*values_ptr).to_string_lossy().into_owned());
+                    values_ptr = values_ptr.add(1);
+                }
+            }
+            EncoderParameterKind::String { valid_values }
+        }
+        param_type => {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Unsupported_parameter,
+                format!("parameter '{}' has unknown type {}", name, param_type),
+            ))
+        }
+    };
+    let has_default = lh::heif_encoder_has_default(encoder, c_name) != 0;
+    Ok(EncoderParameter {
+        name,
+        kind,
+        has_default,
+    })
+}
+
+impl Encoder {
+    /// Iterate over the generic list of encoder parameters.
+    /// Each encoder may define its own set of parameters.
+    pub fn parameters(&self) -> EncoderParameters<'_> {
+        EncoderParameters {
+            encoder: self.inner,
+            next: unsafe { lh::heif_encoder_list_parameters(self.inner) },
+            phantom: PhantomData,
+        }
+    }
+
+    /// Find encoder parameter by name.
+    pub fn parameter(&self, name: &str) -> Result<Option<EncoderParameter>> {
+        for param in self.parameters() {
+            let param = param?;
+            if param.name == name {
+                return Ok(Some(param));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get current value of encoder parameter.
+    pub fn get(&self, name: &str) -> Result<ParamValue> {
+        let param = self.existing_parameter(name)?;
+        let c_name = str_to_cstring(name)?;
+        let value = match param.kind {
+            EncoderParameterKind::Integer { .. } => {
+                let mut value = 0;
+                let err = unsafe {
+                    lh::heif_encoder_get_parameter_integer(self.inner, c_name.as_ptr(), &mut value)
+                };
+                HeifError::from_heif_error(err)?;
+                ParamValue::Integer(value)
+            }
+            EncoderParameterKind::Boolean => {
+                let mut value = 0;
+                let err = unsafe {
+                    lh::heif_encoder_get_parameter_boolean(self.inner, c_name.as_ptr(), &mut value)
+                };
+                HeifError::from_heif_error(err)?;
+                ParamValue::Boolean(value != 0)
+            }
+            EncoderParameterKind::String { .. } => ParamValue::String(self.get_string(&c_name)?),
+        };
+        Ok(value)
+    }
+
+    /// Plugins don't report length of string values and may truncate
+    /// them without NUL terminator, so the buffer grows until it fits the value.
+    fn get_string(&self, c_name: &CStr) -> Result<String> {
+        let mut buffer = vec![0u8; 256];
+        loop {
+            let err = unsafe {
+                lh::heif_encoder_get_parameter_string(
+                    self.inner,
+                    c_name.as_ptr(),
+                    buffer.as_mut_ptr() as _,
+                    buffer.len() as _,
+                )
+            };
+            HeifError::from_heif_error(err)?;
+            if let Ok(value) = CStr::from_bytes_until_nul(&buffer) {
+                return Ok(value.to_string_lossy().into_owned());
+            }
+            if buffer.len() >= MAX_STRING_VALUE_SIZE {
+                return Err(HeifError::new(
+                    lh::heif_error_code_heif_error_Encoder_plugin_error,
+                    lh::heif_suberror_code_heif_suberror_Unspecified,
+                    format!(
+                        "value of parameter {:?} doesn't fit into {} bytes",
+                        c_name, MAX_STRING_VALUE_SIZE
+                    ),
+                ));
+            }
+            buffer = vec![0u8; buffer.len() * 4];
+        }
+    }
+
+    /// Set value of encoder parameter. The value is validated against
+    /// type and valid values of the parameter before passing into `libheif`.
+    pub fn set(&mut self, name: &str, value: ParamValue) -> Result<()> {
+        let param = self.existing_parameter(name)?;
+        param.validate(&value)?;
+        let c_name = str_to_cstring(name)?;
+        let err = match value {
+            ParamValue::Integer(v) => unsafe {
+                lh::heif_encoder_set_parameter_integer(self.inner, c_name.as_ptr(), v)
+            },
+            ParamValue::Boolean(v) => unsafe {
+                lh::heif_encoder_set_parameter_boolean(self.inner, c_name.as_ptr(), v.into())
+            },
+            ParamValue::String(v) => {
+                let c_value = str_to_cstring(&v)?;
+                unsafe {
+                    lh::heif_encoder_set_parameter_string(
+                        self.inner,
+                        c_name.as_ptr(),
+                        c_value.as_ptr(),
+                    )
+                }
+            }
+        };
+        HeifError::from_heif_error(err)
+    }
+
+    fn existing_parameter(&self, name: &str) -> Result<EncoderParameter> {
+        self.parameter(name)?.ok_or_else(|| {
+            HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Unsupported_parameter,
+                format!("encoder has no parameter '{}'", name),
+            )
+        })
+    }
+}
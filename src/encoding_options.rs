@@ -0,0 +1,164 @@
+use crate as lh;
+use crate::color_profile::CNclxProfile;
+use crate::enums::{ChromaDownsamplingAlgorithm, ChromaUpsamplingAlgorithm, Orientation};
+use crate::{HeifError, NclxProfile, Result};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ColorConversionOptions {
+    pub preferred_chroma_downsampling_algorithm: ChromaDownsamplingAlgorithm,
+    pub preferred_chroma_upsampling_algorithm: ChromaUpsamplingAlgorithm,
+    /// When set to `false` libheif may also use a different algorithm
+    /// if the preferred one is not available.
+    pub only_use_preferred_chroma_algorithm: bool,
+}
+
+impl From<ColorConversionOptions> for lh::heif_color_conversion_options {
+    fn from(options: ColorConversionOptions) -> Self {
+        Self {
+            version: 1,
+            preferred_chroma_downsampling_algorithm: options
+                .preferred_chroma_downsampling_algorithm
+                .into(),
+            preferred_chroma_upsampling_algorithm: options
+                .preferred_chroma_upsampling_algorithm
+                .into(),
+            only_use_preferred_chroma_algorithm: options.only_use_preferred_chroma_algorithm.into(),
+        }
+    }
+}
+
+/// Builder of options used by encoding of images.
+///
+/// Owns the `heif_encoding_options` allocated by `libheif`
+/// and the output NCLX profile referenced from them.
+pub struct EncodingOptions {
+    inner: *mut lh::heif_encoding_options,
+    output_nclx_profile: Option<CNclxProfile>,
+    /// The first error of setting of options, returned by `as_ptr()`.
+    error: Option<HeifError>,
+}
+
+impl EncodingOptions {
+    pub fn new() -> Result<Self> {
+        let inner = unsafe { lh::heif_encoding_options_alloc() };
+        if inner.is_null() {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Memory_allocation_error,
+                lh::heif_suberror_code_heif_suberror_Unspecified,
+                "heif_encoding_options_alloc() returned NULL",
+            ));
+        }
+        Ok(Self {
+            inner,
+            output_nclx_profile: None,
+            error: None,
+        })
+    }
+
+    /// Default: `true`.
+    pub fn save_alpha_channel(mut self, enable: bool) -> Self {
+        if let Some(options) = self.options_mut(1, "save_alpha_channel") {
+            options.save_alpha_channel = enable.into();
+        }
+        self
+    }
+
+    /// Default: `false`.
+    pub fn save_two_colr_boxes_when_icc_and_nclx_available(mut self, enable: bool) -> Self {
+        if let Some(options) =
+            self.options_mut(3, "save_two_colr_boxes_when_ICC_and_nclx_available")
+        {
+            options.save_two_colr_boxes_when_ICC_and_nclx_available = enable.into();
+        }
+        self
+    }
+
+    /// NCLX parameters to be used in the output image. If it is not set,
+    /// the same parameters as in the input image will be used.
+    /// Values unsupported by `libheif` are reported by encoding.
+    /// The profile is stored in `colr` box only if
+    /// [`EncodingOptions::macos_compatibility_workaround_no_nclx_profile()`]
+    /// is disabled.
+    pub fn output_nclx_profile(mut self, profile: NclxProfile) -> Self {
+        let profile = match CNclxProfile::new(&profile) {
+            Ok(profile) => profile,
+            Err(err) => {
+                self.error.get_or_insert(err);
+                return self;
+            }
+        };
+        match self.options_mut(3, "output_nclx_profile") {
+            Some(options) => options.output_nclx_profile = profile.inner,
+            None => return self,
+        }
+        self.output_nclx_profile = Some(profile);
+        self
+    }
+
+    /// Don't write NCLX profile into the output image, because
+    /// some versions of macOS and iOS can't read images with it.
+    /// Default: `true`.
+    pub fn macos_compatibility_workaround_no_nclx_profile(mut self, enable: bool) -> Self {
+        if let Some(options) = self.options_mut(4, "macOS_compatibility_workaround_no_nclx_profile")
+        {
+            options.macOS_compatibility_workaround_no_nclx_profile = enable.into();
+        }
+        self
+    }
+
+    /// `libheif` will generate irot/imir boxes to match this orientation.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        if let Some(options) = self.options_mut(5, "image_orientation") {
+            options.image_orientation = orientation.into();
+        }
+        self
+    }
+
+    pub fn color_conversion_options(mut self, conversion_options: ColorConversionOptions) -> Self {
+        if let Some(options) = self.options_mut(6, "color_conversion_options") {
+            options.color_conversion_options = conversion_options.into();
+        }
+        self
+    }
+
+    /// Returns options only if the allocated structure is new enough to
+    /// contain the field, otherwise remembers error about unsupported option.
+    fn options_mut(
+        &mut self,
+        min_version: u8,
+        option_name: &'static str,
+    ) -> Option<&mut lh::heif_encoding_options> {
+        let options = unsafe { &mut *self.inner };
+        if options.version >= min_version {
+            Some(options)
+        } else {
+            self.error.get_or_insert_with(|| {
+                HeifError::new(
+                    lh::heif_error_code_heif_error_Unsupported_feature,
+                    lh::heif_suberror_code_heif_suberror_Unsupported_parameter,
+                    format!(
+                        "encoding option '{}' is not supported by linked libheif",
+                        option_name
+                    ),
+                )
+            });
+            None
+        }
+    }
+
+    /// Pointer to options that may be passed into `libheif` functions.
+    /// Returns error if some of options isn't supported by linked version
+    /// of `libheif` or has invalid value.
+    pub(crate) fn as_ptr(&self) -> Result<*const lh::heif_encoding_options> {
+        match &self.error {
+            Some(err) => Err(err.clone()),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+impl Drop for EncodingOptions {
+    fn drop(&mut self) {
+        unsafe { lh::heif_encoding_options_free(self.inner) };
+    }
+}
@@ -0,0 +1,14 @@
+//! Rust enums for families of `libheif` constants.
+//!
+//! Enums are generated by the build script from the same "bindings.rs"
+//! that is used by the crate, so they always correspond to the version
+//! of `libheif` headers. Every enum has conversion into the raw C type
+//! with help of `From` and conversion from it with help of `TryFrom`.
+//!
+//! Color primaries, transfer characteristics and matrix coefficients
+//! of NCLX profile are defined by hand-written enums
+//! [`ColorPrimaries`](crate::ColorPrimaries),
+//! [`TransferCharacteristics`](crate::TransferCharacteristics)
+//! and [`MatrixCoefficients`](crate::MatrixCoefficients).
+
+include!(concat!(env!("OUT_DIR"), "/enums.rs"));
@@ -0,0 +1,54 @@
+use std::ffi::CStr;
+use std::fmt;
+
+use crate as lh;
+
+/// Error returned by safe wrappers around `libheif` functions.
+///
+/// Codes are kept as raw `libheif` values, so they can be compared
+/// with `heif_error_code_*` and `heif_suberror_code_*` constants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeifError {
+    pub code: lh::heif_error_code,
+    pub sub_code: lh::heif_suberror_code,
+    pub message: String,
+}
+
+pub type Result<T> = std::result::Result<T, HeifError>;
+
+impl HeifError {
+    pub(crate) fn new(
+        code: lh::heif_error_code,
+        sub_code: lh::heif_suberror_code,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            code,
+            sub_code,
+            message: message.into(),
+        }
+    }
+
+    /// Converts `heif_error` returned by `libheif` into `Result`.
+    pub fn from_heif_error(err: lh::heif_error) -> Result<()> {
+        if err.code == lh::heif_error_code_heif_error_Ok {
+            return Ok(());
+        }
+        let message = if err.message.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(err.message) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        Err(Self::new(err.code, err.subcode, message))
+    }
+}
+
+impl std::error::Error for HeifError {}
+
+impl fmt::Display for HeifError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({}) {}", self.code, self.sub_code, self.message)
+    }
+}
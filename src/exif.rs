@@ -0,0 +1,139 @@
+use std::ops::Range;
+
+use crate as lh;
+use crate::{HeifError, Result};
+
+const GPS_INFO_TAG: u16 = 0x8825;
+const IFD_ENTRY_SIZE: usize = 12;
+
+/// Mutable view of TIFF data of Exif metadata.
+struct Tiff<'a> {
+    data: &'a mut [u8],
+    little_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(data: &'a mut [u8]) -> Result<Self> {
+        let little_endian = if data.starts_with(b"II*\0") {
+            true
+        } else if data.starts_with(b"MM\0*") {
+            false
+        } else {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Invalid_input,
+                lh::heif_suberror_code_heif_suberror_Unspecified,
+                "Exif data doesn't start with TIFF header",
+            ));
+        };
+        Ok(Self {
+            data,
+            little_endian,
+        })
+    }
+
+    fn bytes(&self, range: Range<usize>) -> Result<&[u8]> {
+        self.data.get(range).ok_or_else(end_of_data)
+    }
+
+    fn u16_at(&self, pos: usize) -> Result<u16> {
+        let bytes = self.bytes(pos..pos + 2)?;
+        let bytes = [bytes[0], bytes[1]];
+        Ok(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32_at(&self, pos: usize) -> Result<usize> {
+        let bytes = self.bytes(pos..pos + 4)?;
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Ok(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        } as usize)
+    }
+
+    fn set_u16_at(&mut self, pos: usize, value: u16) -> Result<()> {
+        let bytes = if self.little_endian {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        };
+        let dst = self.data.get_mut(pos..pos + 2).ok_or_else(end_of_data)?;
+        dst.copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    fn zero(&mut self, range: Range<usize>) -> Result<()> {
+        let bytes = self.data.get_mut(range).ok_or_else(end_of_data)?;
+        bytes.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    /// Range of IFD bytes including number of entries
+    /// and offset of the next IFD.
+    fn ifd_range(&self, offset: usize) -> Result<Range<usize>> {
+        let count = self.u16_at(offset)? as usize;
+        let end = offset + 2 + count * IFD_ENTRY_SIZE + 4;
+        self.bytes(offset..end)?;
+        Ok(offset..end)
+    }
+}
+
+/// Removes GPS IFD from TIFF data of Exif metadata.
+///
+/// The size of data isn't changed, removed entries and values are
+/// overwritten by zeros. Returns `false` if data doesn't contain GPS IFD.
+pub(crate) fn strip_gps(data: &mut [u8]) -> Result<bool> {
+    let mut tiff = Tiff::new(data)?;
+    let ifd0 = tiff.ifd_range(tiff.u32_at(4)?)?;
+    let count = tiff.u16_at(ifd0.start)?;
+    let entries_start = ifd0.start + 2;
+    let gps_entry = (0..count as usize)
+        .map(|i| entries_start + i * IFD_ENTRY_SIZE)
+        .find(|&pos| matches!(tiff.u16_at(pos), Ok(GPS_INFO_TAG)));
+    let gps_entry = match gps_entry {
+        Some(pos) => pos,
+        None => return Ok(false),
+    };
+
+    let gps_ifd = tiff.ifd_range(tiff.u32_at(gps_entry + 8)?)?;
+    let gps_count = tiff.u16_at(gps_ifd.start)?;
+    for i in 0..gps_count as usize {
+        let pos = gps_ifd.start + 2 + i * IFD_ENTRY_SIZE;
+        let size = value_type_size(tiff.u16_at(pos + 2)?).saturating_mul(tiff.u32_at(pos + 4)?);
+        if size > 4 {
+            let offset = tiff.u32_at(pos + 8)?;
+            tiff.zero(offset..offset.saturating_add(size))?;
+        }
+    }
+    tiff.zero(gps_ifd)?;
+
+    // Shift the rest of entries and offset of the next IFD over GPS entry.
+    tiff.data
+        .copy_within(gps_entry + IFD_ENTRY_SIZE..ifd0.end, gps_entry);
+    tiff.zero(ifd0.end - IFD_ENTRY_SIZE..ifd0.end)?;
+    tiff.set_u16_at(ifd0.start, count - 1)?;
+    Ok(true)
+}
+
+/// Size of one value of TIFF field type.
+fn value_type_size(value_type: u16) -> usize {
+    match value_type {
+        1 | 2 | 6 | 7 => 1,
+        3 | 8 => 2,
+        4 | 9 | 11 => 4,
+        5 | 10 | 12 => 8,
+        _ => 0,
+    }
+}
+
+fn end_of_data() -> HeifError {
+    HeifError::new(
+        lh::heif_error_code_heif_error_Invalid_input,
+        lh::heif_suberror_code_heif_suberror_End_of_data,
+        "unexpected end of TIFF data",
+    )
+}
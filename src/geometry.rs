@@ -0,0 +1,166 @@
+use crate as lh;
+use crate::{probe, HeifError, Image, ImageHandle, Result};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Size {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Ratio of horizontal and vertical spacing of pixels.
+/// Square pixels have ratio 1:1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PixelAspectRatio {
+    pub h_spacing: u32,
+    pub v_spacing: u32,
+}
+
+impl Default for PixelAspectRatio {
+    fn default() -> Self {
+        Self {
+            h_spacing: 1,
+            v_spacing: 1,
+        }
+    }
+}
+
+impl PixelAspectRatio {
+    pub fn is_square(&self) -> bool {
+        self.h_spacing == self.v_spacing
+    }
+
+    /// Stretch size of an image so that its pixels become square.
+    /// Images are only enlarged to not lose any pixels.
+    pub fn apply(&self, size: Size) -> Size {
+        let (h, v) = (self.h_spacing as u64, self.v_spacing as u64);
+        if h == 0 || v == 0 || h == v {
+            return size;
+        }
+        let stretch = |value: u32, num: u64, den: u64| {
+            ((value as u64 * num + den / 2) / den).min(u32::MAX as u64) as u32
+        };
+        if h > v {
+            Size {
+                width: stretch(size.width, h, v),
+                height: size.height,
+            }
+        } else {
+            Size {
+                width: size.width,
+                height: stretch(size.height, v, h),
+            }
+        }
+    }
+}
+
+/// Sizes of an image required to display it properly.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DisplayGeometry {
+    /// Size stored in `ispe` property, i.e. size of the coded image
+    /// before any transformations.
+    pub ispe: Size,
+    /// Size with clean aperture and rotation applied.
+    pub cropped: Size,
+    /// Counter-clockwise rotation from `irot` property in degrees.
+    pub rotation: u32,
+    pub pixel_aspect_ratio: PixelAspectRatio,
+    /// Cropped size corrected by pixel aspect ratio, which is applied
+    /// before rotation of the image.
+    pub display: Size,
+}
+
+/// Transformative property of an item which changes its size.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Transformation {
+    /// Size of clean aperture from `clap` property.
+    CleanAperture(Size),
+    /// Number of counter-clockwise quarter turns from `irot` property.
+    Rotation(u8),
+}
+
+impl DisplayGeometry {
+    /// Transformations must be in order of their association
+    /// with the item, in which they are applied.
+    pub(crate) fn new(
+        ispe: Size,
+        pixel_aspect_ratio: PixelAspectRatio,
+        transformations: &[Transformation],
+    ) -> Self {
+        // Size in orientation of the coded image.
+        let mut size = ispe;
+        let mut quarter_turns = 0;
+        for transformation in transformations {
+            match *transformation {
+                Transformation::CleanAperture(aperture) => {
+                    size = transpose(aperture, quarter_turns);
+                }
+                Transformation::Rotation(turns) => quarter_turns = (quarter_turns + turns) % 4,
+            }
+        }
+        Self {
+            ispe,
+            cropped: transpose(size, quarter_turns),
+            rotation: quarter_turns as u32 * 90,
+            pixel_aspect_ratio,
+            // Pixel aspect ratio is defined for the image before rotation.
+            display: transpose(pixel_aspect_ratio.apply(size), quarter_turns),
+        }
+    }
+}
+
+fn transpose(size: Size, quarter_turns: u8) -> Size {
+    if quarter_turns & 1 == 0 {
+        size
+    } else {
+        Size {
+            width: size.height,
+            height: size.width,
+        }
+    }
+}
+
+impl Image {
+    /// Pixel aspect ratio of the image, 1:1 if it isn't set.
+    pub fn pixel_aspect_ratio(&self) -> PixelAspectRatio {
+        let mut res = PixelAspectRatio::default();
+        unsafe {
+            lh::heif_image_get_pixel_aspect_ratio(
+                self.inner,
+                &mut res.h_spacing,
+                &mut res.v_spacing,
+            )
+        };
+        res
+    }
+
+    /// Set pixel aspect ratio which will be stored in `pasp` property
+    /// during encoding of the image.
+    pub fn set_pixel_aspect_ratio(&mut self, ratio: PixelAspectRatio) {
+        unsafe {
+            lh::heif_image_set_pixel_aspect_ratio(self.inner, ratio.h_spacing, ratio.v_spacing)
+        };
+    }
+}
+
+impl ImageHandle {
+    /// Geometry of the image read from properties of its item
+    /// in `data`, which must be the file the handle was read from.
+    ///
+    /// The image isn't decoded, sizes are computed from `ispe`, `clap`,
+    /// `irot` and `pasp` properties parsed by [`probe`](crate::probe).
+    pub fn display_geometry(&self, data: &[u8]) -> Result<DisplayGeometry> {
+        let item_id = self.item_id();
+        probe(data)?
+            .items
+            .into_iter()
+            .find(|item| item.id == item_id)
+            .and_then(|item| item.geometry)
+            .ok_or_else(|| {
+                HeifError::new(
+                    lh::heif_error_code_heif_error_Invalid_input,
+                    lh::heif_suberror_code_heif_suberror_No_properties_assigned_to_item,
+                    "data doesn't contain 'ispe' property of the image item",
+                )
+            })
+    }
+}
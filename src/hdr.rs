@@ -0,0 +1,172 @@
+use crate as lh;
+use crate::{HeifError, Image, Result};
+
+/// Chromaticities are stored in increments of 0.00002.
+const CHROMATICITY_SCALE: f32 = 50_000.0;
+/// Luminance is stored in increments of 0.0001 cd/m².
+const LUMINANCE_SCALE: f64 = 10_000.0;
+
+/// HDR metadata attached to an image.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct HdrMetadata {
+    pub clli: Option<ContentLightLevel>,
+    pub mdcv: Option<MasteringDisplay>,
+}
+
+/// Content light level information.
+/// Values are in cd/m², zero indicates that a value is undefined.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ContentLightLevel {
+    pub max_content_light_level: u16,
+    pub max_pic_average_light_level: u16,
+}
+
+/// Color coordinates in CIE 1931 xy space.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Chromaticity {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Colour volume of the display used for mastering of the content.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct MasteringDisplay {
+    /// Primaries of the display in order as they are stored in the file,
+    /// usually green, blue and red.
+    pub display_primaries: [Chromaticity; 3],
+    pub white_point: Chromaticity,
+    /// Maximal luminance of the display in cd/m².
+    pub max_luminance: f64,
+    /// Minimal luminance of the display in cd/m².
+    pub min_luminance: f64,
+}
+
+impl MasteringDisplay {
+    fn encode(&self) -> Result<lh::heif_mastering_display_colour_volume> {
+        let [p0, p1, p2] = self.display_primaries;
+        Ok(lh::heif_mastering_display_colour_volume {
+            display_primaries_x: [
+                encode_chromaticity(p0.x)?,
+                encode_chromaticity(p1.x)?,
+                encode_chromaticity(p2.x)?,
+            ],
+            display_primaries_y: [
+                encode_chromaticity(p0.y)?,
+                encode_chromaticity(p1.y)?,
+                encode_chromaticity(p2.y)?,
+            ],
+            white_point_x: encode_chromaticity(self.white_point.x)?,
+            white_point_y: encode_chromaticity(self.white_point.y)?,
+            max_display_mastering_luminance: encode_luminance(self.max_luminance)?,
+            min_display_mastering_luminance: encode_luminance(self.min_luminance)?,
+        })
+    }
+}
+
+impl From<&lh::heif_decoded_mastering_display_colour_volume> for MasteringDisplay {
+    fn from(mdcv: &lh::heif_decoded_mastering_display_colour_volume) -> Self {
+        let primary = |i: usize| Chromaticity {
+            x: mdcv.display_primaries_x[i],
+            y: mdcv.display_primaries_y[i],
+        };
+        Self {
+            display_primaries: [primary(0), primary(1), primary(2)],
+            white_point: Chromaticity {
+                x: mdcv.white_point_x,
+                y: mdcv.white_point_y,
+            },
+            max_luminance: mdcv.max_display_mastering_luminance,
+            min_luminance: mdcv.min_display_mastering_luminance,
+        }
+    }
+}
+
+fn encode_chromaticity(value: f32) -> Result<u16> {
+    if !(0.0..=1.0).contains(&value) {
+        return Err(invalid_value(format!(
+            "chromaticity coordinate {} is out of range 0.0-1.0",
+            value
+        )));
+    }
+    Ok((value * CHROMATICITY_SCALE).round() as u16)
+}
+
+fn encode_luminance(value: f64) -> Result<u32> {
+    let encoded = (value * LUMINANCE_SCALE).round();
+    if !(0.0..=u32::MAX as f64).contains(&encoded) {
+        return Err(invalid_value(format!(
+            "luminance {} is out of range",
+            value
+        )));
+    }
+    Ok(encoded as u32)
+}
+
+fn invalid_value(message: String) -> HeifError {
+    HeifError::new(
+        lh::heif_error_code_heif_error_Usage_error,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+        message,
+    )
+}
+
+impl Image {
+    /// Content light level and mastering display colour volume of the image.
+    pub fn hdr_metadata(&self) -> Result<HdrMetadata> {
+        let mut res = HdrMetadata::default();
+        if unsafe { lh::heif_image_has_content_light_level(self.inner) } != 0 {
+            let mut clli = lh::heif_content_light_level {
+                max_content_light_level: 0,
+                max_pic_average_light_level: 0,
+            };
+            unsafe { lh::heif_image_get_content_light_level(self.inner, &mut clli) };
+            res.clli = Some(ContentLightLevel {
+                max_content_light_level: clli.max_content_light_level,
+                max_pic_average_light_level: clli.max_pic_average_light_level,
+            });
+        }
+        if unsafe { lh::heif_image_has_mastering_display_colour_volume(self.inner) } != 0 {
+            let mut mdcv = lh::heif_mastering_display_colour_volume {
+                display_primaries_x: [0; 3],
+                display_primaries_y: [0; 3],
+                white_point_x: 0,
+                white_point_y: 0,
+                max_display_mastering_luminance: 0,
+                min_display_mastering_luminance: 0,
+            };
+            let mut decoded = lh::heif_decoded_mastering_display_colour_volume {
+                display_primaries_x: [0.0; 3],
+                display_primaries_y: [0.0; 3],
+                white_point_x: 0.0,
+                white_point_y: 0.0,
+                max_display_mastering_luminance: 0.0,
+                min_display_mastering_luminance: 0.0,
+            };
+            let err = unsafe {
+                lh::heif_image_get_mastering_display_colour_volume(self.inner, &mut mdcv);
+                lh::heif_mastering_display_colour_volume_decode(&mdcv, &mut decoded)
+            };
+            HeifError::from_heif_error(err)?;
+            res.mdcv = Some((&decoded).into());
+        }
+        Ok(res)
+    }
+
+    /// Attach HDR metadata to the image. Values which are `None`
+    /// don't change the metadata already attached to the image.
+    pub fn set_hdr_metadata(&mut self, metadata: &HdrMetadata) -> Result<()> {
+        // Encode all values before changing of the image.
+        let mdcv = metadata.mdcv.as_ref().map(|m| m.encode()).transpose()?;
+        if let Some(clli) = metadata.clli {
+            let clli = lh::heif_content_light_level {
+                max_content_light_level: clli.max_content_light_level,
+                max_pic_average_light_level: clli.max_pic_average_light_level,
+            };
+            unsafe { lh::heif_image_set_content_light_level(self.inner, &clli) };
+        }
+        if let Some(mdcv) = mdcv {
+            unsafe { lh::heif_image_set_mastering_display_colour_volume(self.inner, &mdcv) };
+        }
+        Ok(())
+    }
+}
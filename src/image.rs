@@ -0,0 +1,171 @@
+use std::convert::TryFrom;
+use std::ptr;
+use std::slice;
+
+use crate as lh;
+use crate::enums::{Channel, Chroma, ColorSpace};
+use crate::utils::to_c_int;
+use crate::{HeifError, Result};
+
+/// Pixel data of one channel of an image.
+#[derive(Debug)]
+pub struct Plane<T> {
+    pub data: T,
+    pub width: u32,
+    pub height: u32,
+    /// Number of bytes between starts of adjacent rows.
+    pub stride: usize,
+    /// Number of bits used for representing the pixel value.
+    pub bits_per_pixel: u8,
+    /// Number of bits used for storing the pixel value in memory.
+    pub storage_bits_per_pixel: u8,
+}
+
+impl Plane<()> {
+    fn with_data<T>(self, data: T) -> Plane<T> {
+        Plane {
+            data,
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            bits_per_pixel: self.bits_per_pixel,
+            storage_bits_per_pixel: self.storage_bits_per_pixel,
+        }
+    }
+}
+
+/// Safe owner of decoded or created `heif_image`.
+pub struct Image {
+    pub(crate) inner: *mut lh::heif_image,
+}
+
+impl Image {
+    /// Create a new image of given size, color space and chroma.
+    /// Planes must be added with [`Image::add_plane`] before filling pixels.
+    pub fn new(width: u32, height: u32, color_space: ColorSpace, chroma: Chroma) -> Result<Self> {
+        let mut c_image = ptr::null_mut();
+        let err = unsafe {
+            lh::heif_image_create(
+                to_c_int(width)?,
+                to_c_int(height)?,
+                color_space.into(),
+                chroma.into(),
+                &mut c_image,
+            )
+        };
+        HeifError::from_heif_error(err)?;
+        Ok(Self { inner: c_image })
+    }
+
+    /// Add a new plane of given size and bit depth to the image.
+    pub fn add_plane(
+        &mut self,
+        channel: Channel,
+        width: u32,
+        height: u32,
+        bit_depth: u8,
+    ) -> Result<()> {
+        let err = unsafe {
+            lh::heif_image_add_plane(
+                self.inner,
+                channel.into(),
+                to_c_int(width)?,
+                to_c_int(height)?,
+                bit_depth.into(),
+            )
+        };
+        HeifError::from_heif_error(err)
+    }
+
+    /// Width of the main channel.
+    pub fn width(&self) -> u32 {
+        unsafe { lh::heif_image_get_primary_width(self.inner) }.max(0) as u32
+    }
+
+    /// Height of the main channel.
+    pub fn height(&self) -> u32 {
+        unsafe { lh::heif_image_get_primary_height(self.inner) }.max(0) as u32
+    }
+
+    pub fn color_space(&self) -> Option<ColorSpace> {
+        ColorSpace::try_from(unsafe { lh::heif_image_get_colorspace(self.inner) }).ok()
+    }
+
+    pub fn chroma(&self) -> Option<Chroma> {
+        Chroma::try_from(unsafe { lh::heif_image_get_chroma_format(self.inner) }).ok()
+    }
+
+    pub fn has_channel(&self, channel: Channel) -> bool {
+        unsafe { lh::heif_image_has_channel(self.inner, channel.into()) != 0 }
+    }
+
+    pub fn plane(&self, channel: Channel) -> Option<Plane<&[u8]>> {
+        let mut stride = 0;
+        let data =
+            unsafe { lh::heif_image_get_plane_readonly(self.inner, channel.into(), &mut stride) };
+        let plane = self.plane_info(channel, data.is_null(), stride)?;
+        let size = plane.stride * plane.height as usize;
+        Some(plane.with_data(unsafe { slice::from_raw_parts(data, size) }))
+    }
+
+    pub fn plane_mut(&mut self, channel: Channel) -> Option<Plane<&mut [u8]>> {
+        let mut stride = 0;
+        let data = unsafe { lh::heif_image_get_plane(self.inner, channel.into(), &mut stride) };
+        let plane = self.plane_info(channel, data.is_null(), stride)?;
+        let size = plane.stride * plane.height as usize;
+        Some(plane.with_data(unsafe { slice::from_raw_parts_mut(data, size) }))
+    }
+
+    fn plane_info(
+        &self,
+        channel: Channel,
+        is_null: bool,
+        stride: libc::c_int,
+    ) -> Option<Plane<()>> {
+        let c_channel = channel.into();
+        let width = unsafe { lh::heif_image_get_width(self.inner, c_channel) };
+        let height = unsafe { lh::heif_image_get_height(self.inner, c_channel) };
+        if is_null || width < 0 || height < 0 || stride < 0 {
+            return None;
+        }
+        let bits_per_pixel =
+            unsafe { lh::heif_image_get_bits_per_pixel_range(self.inner, c_channel) };
+        let storage_bits_per_pixel =
+            unsafe { lh::heif_image_get_bits_per_pixel(self.inner, c_channel) };
+        Some(Plane {
+            data: (),
+            width: width as u32,
+            height: height as u32,
+            stride: stride as usize,
+            bits_per_pixel: bits_per_pixel.max(0) as u8,
+            storage_bits_per_pixel: storage_bits_per_pixel.max(0) as u8,
+        })
+    }
+
+    /// Create a scaled copy of the image.
+    pub fn scale(&self, width: u32, height: u32) -> Result<Image> {
+        let mut c_image = ptr::null_mut();
+        let err = unsafe {
+            lh::heif_image_scale_image(
+                self.inner,
+                &mut c_image,
+                to_c_int(width)?,
+                to_c_int(height)?,
+                ptr::null(),
+            )
+        };
+        HeifError::from_heif_error(err)?;
+        Ok(Image { inner: c_image })
+    }
+
+    /// Raw pointer to the underlying `heif_image`.
+    pub fn as_ptr(&self) -> *mut lh::heif_image {
+        self.inner
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        unsafe { lh::heif_image_release(self.inner) };
+    }
+}
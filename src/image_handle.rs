@@ -0,0 +1,86 @@
+use std::ptr;
+
+use crate as lh;
+use crate::{Chroma, ColorSpace, DecodingOptions, HeifError, Image, Result};
+
+/// Safe owner of `heif_image_handle`.
+///
+/// Image handle keeps alive the context from which it was obtained,
+/// so it may outlive [`HeifContext`](crate::HeifContext).
+pub struct ImageHandle {
+    pub(crate) inner: *mut lh::heif_image_handle,
+}
+
+impl ImageHandle {
+    pub fn item_id(&self) -> lh::heif_item_id {
+        unsafe { lh::heif_image_handle_get_item_id(self.inner) }
+    }
+
+    /// Check whether the image is the primary image of the file.
+    pub fn is_primary(&self) -> bool {
+        unsafe { lh::heif_image_handle_is_primary_image(self.inner) != 0 }
+    }
+
+    /// Width of the image, with transformations applied.
+    pub fn width(&self) -> u32 {
+        unsafe { lh::heif_image_handle_get_width(self.inner) }.max(0) as u32
+    }
+
+    /// Height of the image, with transformations applied.
+    pub fn height(&self) -> u32 {
+        unsafe { lh::heif_image_handle_get_height(self.inner) }.max(0) as u32
+    }
+
+    pub fn has_alpha_channel(&self) -> bool {
+        unsafe { lh::heif_image_handle_has_alpha_channel(self.inner) != 0 }
+    }
+
+    /// Decode the image into the given color space and chroma.
+    pub fn decode(&self, color_space: ColorSpace, chroma: Chroma) -> Result<Image> {
+        let mut c_image = ptr::null_mut();
+        let err = unsafe {
+            lh::heif_decode_image(
+                self.inner,
+                &mut c_image,
+                color_space.into(),
+                chroma.into(),
+                ptr::null(),
+            )
+        };
+        HeifError::from_heif_error(err)?;
+        Ok(Image { inner: c_image })
+    }
+
+    /// Decode the image into the given color space and chroma
+    /// with custom decoding options.
+    pub fn decode_with_options(
+        &self,
+        color_space: ColorSpace,
+        chroma: Chroma,
+        options: &DecodingOptions,
+    ) -> Result<Image> {
+        let mut c_image = ptr::null_mut();
+        let err = unsafe {
+            lh::heif_decode_image(
+                self.inner,
+                &mut c_image,
+                color_space.into(),
+                chroma.into(),
+                options.as_ptr()?,
+            )
+        };
+        HeifError::from_heif_error(err)?;
+        Ok(Image { inner: c_image })
+    }
+
+    /// Raw pointer to the underlying `heif_image_handle`.
+    pub fn as_ptr(&self) -> *mut lh::heif_image_handle {
+        self.inner
+    }
+}
+
+impl Drop for ImageHandle {
+    fn drop(&mut self) {
+        unsafe { lh::heif_image_handle_release(self.inner) };
+    }
+}
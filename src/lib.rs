@@ -10,3 +10,74 @@ include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 mod bindings;
 #[cfg(any(not(feature = "use-bindgen"), docs_rs))]
 pub use bindings::*;
+
+pub use auxiliary::{AuxiliaryImage, AuxiliaryImages, AuxiliaryKind};
+pub use capabilities::{capabilities, Capabilities, DecoderCapability, EncoderCapability};
+pub use color_profile::{
+    ColorPrimaries, ColorProfile, MatrixCoefficients, NclxProfile, TransferCharacteristics,
+};
+pub use context::{HeifContext, TopLevelImage, TopLevelImages};
+pub use context_builder::{CollectionImage, HeifContextBuilder};
+pub use decode::{decode_file_to_rgba8, decode_to_rgb16, decode_to_rgba8, DecodedPixels};
+pub use decoder::DecoderChoice;
+pub use decoding_options::DecodingOptions;
+pub use depth::{DepthImage, DepthImages, DepthRepresentation};
+pub use encode::{
+    encode_interleaved, encode_rgb_to_avif, encode_rgba_to_heic, EncodeSettings, PixelLayout,
+};
+pub use encoder::{encoder_descriptors, Encoder, EncoderDescriptor};
+pub use encoder_parameter::{
+    EncoderParameter, EncoderParameterKind, EncoderParameters, ParamValue,
+};
+pub use encoding_options::{ColorConversionOptions, EncodingOptions};
+pub use enums::{
+    Channel, Chroma, ChromaDownsamplingAlgorithm, ChromaUpsamplingAlgorithm, ColorSpace,
+    CompressionFormat, FileTypeResult, MetadataCompression, Orientation, PluginType,
+};
+pub use error::{HeifError, Result};
+pub use geometry::{DisplayGeometry, PixelAspectRatio, Size};
+pub use hdr::{Chromaticity, ContentLightLevel, HdrMetadata, MasteringDisplay};
+pub use image::{Image, Plane};
+pub use image_handle::ImageHandle;
+pub use metadata::{
+    transfer_color_profile, transfer_metadata, MetadataBlock, MetadataBlocks, MetadataKind,
+    MetadataPolicy,
+};
+pub use plugin::{LoadedPlugin, PluginRegistry};
+pub use probe::{probe, ProbeInfo, ProbeItem, ProbeReference};
+pub use security_limits::SecurityLimits;
+pub use sniff::{sniff, Brand, Sniff};
+pub use thumbnail::Thumbnails;
+
+#[macro_use]
+mod macros;
+
+pub mod enums;
+
+mod auxiliary;
+mod capabilities;
+mod color_profile;
+mod context;
+mod context_builder;
+mod decode;
+mod decoder;
+mod decoding_options;
+mod depth;
+mod encode;
+mod encoder;
+mod encoder_parameter;
+mod encoding_options;
+mod error;
+mod exif;
+mod geometry;
+mod hdr;
+mod image;
+mod image_handle;
+mod metadata;
+mod plugin;
+mod probe;
+mod security_limits;
+mod sniff;
+mod thumbnail;
+mod utils;
+mod xmp;
@@ -0,0 +1,93 @@
+/// Declares Rust enum for a family of `libheif` constants
+/// with conversions from and into the raw C type.
+macro_rules! c_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident: $c_type:ident {
+            $(
+                $(#[$v_meta:meta])*
+                $variant:ident = $c_value:ident,
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[non_exhaustive]
+        pub enum $name {
+            $(
+                $(#[$v_meta])*
+                $variant,
+            )+
+        }
+
+        impl $name {
+            /// All variants of the enum in order of declaration.
+            pub const ALL: &'static [$name] = &[$($name::$variant,)+];
+        }
+
+        impl From<$name> for $crate::$c_type {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $crate::$c_value,)+
+                }
+            }
+        }
+
+        impl std::convert::TryFrom<$crate::$c_type> for $name {
+            type Error = $crate::$c_type;
+
+            fn try_from(value: $crate::$c_type) -> std::result::Result<Self, Self::Error> {
+                match value {
+                    $($crate::$c_value => Ok($name::$variant),)+
+                    _ => Err(value),
+                }
+            }
+        }
+    };
+}
+
+/// Same as [`c_enum!`], but keeps values unknown by the enum
+/// in `Unknown` variant, so conversion from the raw C type never fails.
+macro_rules! c_enum_with_unknown {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident: $c_type:ident {
+            $(
+                $(#[$v_meta:meta])*
+                $variant:ident = $c_value:ident,
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum $name {
+            $(
+                $(#[$v_meta])*
+                $variant,
+            )+
+            /// Value which isn't known by this version of the crate.
+            /// Known values are always converted into other variants.
+            Unknown($crate::$c_type),
+        }
+
+        impl From<$name> for $crate::$c_type {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $crate::$c_value,)+
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<$crate::$c_type> for $name {
+            fn from(value: $crate::$c_type) -> Self {
+                match value {
+                    $($crate::$c_value => $name::$variant,)+
+                    _ => $name::Unknown(value),
+                }
+            }
+        }
+    };
+}
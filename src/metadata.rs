@@ -0,0 +1,347 @@
+use std::ptr;
+
+use crate as lh;
+use crate::enums::MetadataCompression;
+use crate::exif;
+use crate::utils::{cstr_to_string, item_ids, len_to_c_int, str_to_cstring};
+use crate::xmp;
+use crate::{ColorProfile, HeifContext, HeifError, Image, ImageHandle, Result};
+
+const APP1_EXIF_HEADER: &[u8] = b"Exif\0\0";
+
+/// Kind of metadata block detected by its item type and content type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MetadataKind {
+    /// Item type `Exif`.
+    Exif,
+    /// Item type `mime` with content type `application/rdf+xml`.
+    Xmp,
+    /// Item type `mime` with content type `text/xml`.
+    Mpeg7,
+    /// IPTC IIM stream stored with item type `iptc`.
+    Iptc,
+    /// Item type `uri `, the format of data is defined by
+    /// [`MetadataBlock::uri_type`].
+    Uri,
+    Other,
+}
+
+/// Metadata block attached to an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataBlock {
+    pub id: lh::heif_item_id,
+    /// Item type as stored in the file, e.g. `"Exif"`, `"mime"` or `"uri "`.
+    pub item_type: String,
+    /// Content type of `mime` items, empty for other item types.
+    pub content_type: String,
+    /// Absolute URI defining the format of `uri ` items.
+    pub uri_type: Option<String>,
+    /// Raw data exactly as stored in the file. Exif data starts with
+    /// four bytes of offset to the TIFF header.
+    pub data: Vec<u8>,
+}
+
+impl MetadataBlock {
+    pub fn kind(&self) -> MetadataKind {
+        match (self.item_type.as_str(), self.content_type.as_str()) {
+            ("Exif", _) => MetadataKind::Exif,
+            ("mime", "application/rdf+xml") => MetadataKind::Xmp,
+            ("mime", "text/xml") => MetadataKind::Mpeg7,
+            ("iptc", _) => MetadataKind::Iptc,
+            ("uri ", _) => MetadataKind::Uri,
+            _ => MetadataKind::Other,
+        }
+    }
+
+    /// TIFF data of `Exif` block without the leading offset to TIFF header.
+    pub fn exif_tiff_bytes(&self) -> Result<&[u8]> {
+        if self.kind() != MetadataKind::Exif {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+                format!("metadata block has type {:?}, not Exif", self.item_type),
+            ));
+        }
+        let offset = match self.data.get(..4) {
+            Some(bytes) => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+            None => {
+                return Err(invalid_exif(
+                    lh::heif_suberror_code_heif_suberror_End_of_data,
+                    "Exif block is too short",
+                ))
+            }
+        };
+        match self.data.get(4..).and_then(|data| data.get(offset..)) {
+            Some(tiff) if is_tiff_header(tiff) => Ok(tiff),
+            Some(_) => Err(invalid_exif(
+                lh::heif_suberror_code_heif_suberror_Unspecified,
+                "Exif block doesn't contain TIFF header",
+            )),
+            None => Err(invalid_exif(
+                lh::heif_suberror_code_heif_suberror_End_of_data,
+                "offset to TIFF header is out of Exif block",
+            )),
+        }
+    }
+}
+
+fn is_tiff_header(data: &[u8]) -> bool {
+    data.starts_with(b"II*\0") || data.starts_with(b"MM\0*")
+}
+
+fn invalid_exif(sub_code: lh::heif_suberror_code, message: &str) -> HeifError {
+    HeifError::new(
+        lh::heif_error_code_heif_error_Invalid_input,
+        sub_code,
+        message,
+    )
+}
+
+/// Iterator over metadata blocks of an image handle.
+pub struct MetadataBlocks<'a> {
+    handle: &'a ImageHandle,
+    ids: std::vec::IntoIter<lh::heif_item_id>,
+}
+
+impl<'a> Iterator for MetadataBlocks<'a> {
+    type Item = Result<MetadataBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.ids.next()?;
+        Some(read_block(self.handle, id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+fn read_block(handle: &ImageHandle, id: lh::heif_item_id) -> Result<MetadataBlock> {
+    let item_type =
+        cstr_to_string(unsafe { lh::heif_image_handle_get_metadata_type(handle.inner, id) })
+            .unwrap_or_default();
+    let content_type = cstr_to_string(unsafe {
+        lh::heif_image_handle_get_metadata_content_type(handle.inner, id)
+    })
+    .unwrap_or_default();
+    let uri_type = if item_type == "uri " {
+        cstr_to_string(unsafe {
+            lh::heif_image_handle_get_metadata_item_uri_type(handle.inner, id)
+        })
+    } else {
+        None
+    };
+
+    let size = unsafe { lh::heif_image_handle_get_metadata_size(handle.inner, id) };
+    let mut data = vec![0u8; size];
+    if size > 0 {
+        let err =
+            unsafe { lh::heif_image_handle_get_metadata(handle.inner, id, data.as_mut_ptr() as _) };
+        HeifError::from_heif_error(err)?;
+    }
+
+    Ok(MetadataBlock {
+        id,
+        item_type,
+        content_type,
+        uri_type,
+        data,
+    })
+}
+
+impl ImageHandle {
+    /// Iterate over all metadata blocks attached to the image.
+    pub fn metadata(&self) -> MetadataBlocks<'_> {
+        MetadataBlocks {
+            handle: self,
+            ids: self.metadata_ids(ptr::null()).into_iter(),
+        }
+    }
+
+    /// Iterate over metadata blocks with the given item type, e.g. `"Exif"`.
+    pub fn metadata_of_type(&self, item_type: &str) -> Result<MetadataBlocks<'_>> {
+        let c_item_type = str_to_cstring(item_type)?;
+        Ok(MetadataBlocks {
+            handle: self,
+            ids: self.metadata_ids(c_item_type.as_ptr()).into_iter(),
+        })
+    }
+
+    pub(crate) fn metadata_ids(&self, type_filter: *const libc::c_char) -> Vec<lh::heif_item_id> {
+        let count =
+            unsafe { lh::heif_image_handle_get_number_of_metadata_blocks(self.inner, type_filter) };
+        item_ids(count, |ids, count| unsafe {
+            lh::heif_image_handle_get_list_of_metadata_block_IDs(
+                self.inner,
+                type_filter,
+                ids,
+                count,
+            )
+        })
+    }
+}
+
+impl HeifContext {
+    /// Add Exif metadata to an image.
+    ///
+    /// `data` may contain raw TIFF data or payload of JPEG APP1 segment
+    /// starting with `Exif\0\0`. `libheif` prepends the offset to TIFF header
+    /// expected by readers.
+    pub fn add_exif(&mut self, handle: &ImageHandle, data: &[u8]) -> Result<()> {
+        let tiff = if data.starts_with(APP1_EXIF_HEADER) {
+            &data[APP1_EXIF_HEADER.len()..]
+        } else {
+            data
+        };
+        if !is_tiff_header(tiff) {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+                "Exif data must start with TIFF header or \"Exif\\0\\0\"",
+            ));
+        }
+        let err = unsafe {
+            lh::heif_context_add_exif_metadata(
+                self.inner,
+                handle.inner,
+                tiff.as_ptr() as _,
+                len_to_c_int(tiff.len())?,
+            )
+        };
+        HeifError::from_heif_error(err)
+    }
+
+    /// Add XMP metadata to an image.
+    pub fn add_xmp(
+        &mut self,
+        handle: &ImageHandle,
+        xmp: &str,
+        compression: MetadataCompression,
+    ) -> Result<()> {
+        let err = unsafe {
+            lh::heif_context_add_XMP_metadata2(
+                self.inner,
+                handle.inner,
+                xmp.as_ptr() as _,
+                len_to_c_int(xmp.len())?,
+                compression.into(),
+            )
+        };
+        HeifError::from_heif_error(err)
+    }
+
+    /// Add generic, proprietary metadata to an image.
+    ///
+    /// `item_type` identifies the metadata, e.g. `"iptc"` for IPTC IIM stream.
+    /// `content_type` is an additional type of `mime` items.
+    pub fn add_generic_metadata(
+        &mut self,
+        handle: &ImageHandle,
+        item_type: &str,
+        content_type: Option<&str>,
+        data: &[u8],
+    ) -> Result<()> {
+        let c_item_type = str_to_cstring(item_type)?;
+        let c_content_type = content_type.map(str_to_cstring).transpose()?;
+        let err = unsafe {
+            lh::heif_context_add_generic_metadata(
+                self.inner,
+                handle.inner,
+                data.as_ptr() as _,
+                len_to_c_int(data.len())?,
+                c_item_type.as_ptr(),
+                c_content_type.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            )
+        };
+        HeifError::from_heif_error(err)
+    }
+}
+
+/// Defines which metadata is copied by [`transfer_metadata()`]
+/// and [`transfer_color_profile()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MetadataPolicy {
+    /// Copy all metadata blocks and color profile.
+    All,
+    /// Same as [`MetadataPolicy::All`], but GPS IFD is removed from Exif
+    /// and `exif:GPS*` properties are removed from XMP. Transfer fails
+    /// if XMP contains a GPS property which can't be parsed.
+    StripGps,
+    /// Copy only ICC profile.
+    OnlyIcc,
+    /// Copy nothing.
+    StripAll,
+}
+
+/// Copy metadata blocks of `from` image into the image `to` of `to_ctx` context.
+///
+/// Blocks with `uri ` item type are skipped, because `libheif` can't write
+/// the URI of their format.
+///
+/// Color profile must be set into the image before its encoding,
+/// so it is copied by [`transfer_color_profile()`]. Policy
+/// [`MetadataPolicy::OnlyIcc`] doesn't copy any metadata blocks,
+/// therefore the function returns an error for it.
+pub fn transfer_metadata(
+    from: &ImageHandle,
+    to_ctx: &mut HeifContext,
+    to: &ImageHandle,
+    policy: MetadataPolicy,
+) -> Result<()> {
+    match policy {
+        MetadataPolicy::All | MetadataPolicy::StripGps => {}
+        MetadataPolicy::OnlyIcc => {
+            return Err(HeifError::new(
+                lh::heif_error_code_heif_error_Usage_error,
+                lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+                "ICC profile is copied by transfer_color_profile(), \
+                 not by transfer_metadata()",
+            ))
+        }
+        MetadataPolicy::StripAll => return Ok(()),
+    }
+    let strip_gps_data = policy == MetadataPolicy::StripGps;
+    for block in from.metadata() {
+        let block = block?;
+        match block.kind() {
+            MetadataKind::Exif => {
+                let mut tiff = block.exif_tiff_bytes()?.to_vec();
+                if strip_gps_data {
+                    exif::strip_gps(&mut tiff)?;
+                }
+                to_ctx.add_exif(to, &tiff)?;
+            }
+            MetadataKind::Uri => {}
+            kind => {
+                let stripped;
+                let data = if kind == MetadataKind::Xmp && strip_gps_data {
+                    stripped = xmp::strip_gps(&block.data)?;
+                    &stripped
+                } else {
+                    &block.data
+                };
+                let content_type = Some(block.content_type.as_str()).filter(|t| !t.is_empty());
+                to_ctx.add_generic_metadata(to, &block.item_type, content_type, data)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy color profile of `from` image into `to` image before encoding of the latter.
+pub fn transfer_color_profile(
+    from: &ImageHandle,
+    to: &mut Image,
+    policy: MetadataPolicy,
+) -> Result<()> {
+    if policy == MetadataPolicy::StripAll {
+        return Ok(());
+    }
+    match from.color_profile()? {
+        Some(profile @ ColorProfile::Icc { .. }) => to.set_color_profile(profile),
+        Some(profile @ ColorProfile::Nclx(_)) if policy != MetadataPolicy::OnlyIcc => {
+            to.set_color_profile(profile)
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,142 @@
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use crate as lh;
+use crate::enums::PluginType;
+use crate::utils::{cstr_to_string, ensure_initialized, str_to_cstring};
+use crate::{HeifError, Result};
+
+/// Loader of dynamic codec plugins.
+///
+/// Plugins from default directories are loaded automatically
+/// during initialisation of `libheif`.
+pub struct PluginRegistry {
+    _private: (),
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        ensure_initialized();
+        Self { _private: () }
+    }
+
+    /// Directories searched by `libheif` for plugins during initialisation.
+    /// It includes directories from `LIBHEIF_PLUGIN_PATHS` environment variable.
+    pub fn default_directories(&self) -> Vec<PathBuf> {
+        let mut res = Vec::new();
+        let c_dirs = unsafe { lh::heif_get_plugin_directories() };
+        if c_dirs.is_null() {
+            return res;
+        }
+        unsafe {
+            let mut c_dir = c_dirs;
+            while !(*c_dir).is_null() {
+                res.extend(cstr_to_string(*c_dir).map(PathBuf::from));
+                c_dir = c_dir.add(1);
+            }
+            lh::heif_free_plugin_directories(c_dirs);
+        }
+        res
+    }
+
+    /// Load plugin from the given file.
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<LoadedPlugin> {
+        let c_path = str_to_cstring(path_to_str(path.as_ref())?)?;
+        let mut c_info = ptr::null();
+        let err = unsafe { lh::heif_load_plugin(c_path.as_ptr(), &mut c_info) };
+        HeifError::from_heif_error(err)?;
+        Ok(LoadedPlugin { info: c_info })
+    }
+
+    /// Load all plugins from the given directory.
+    pub fn load_dir<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<LoadedPlugin>> {
+        let dir = dir.as_ref();
+        let c_dir = str_to_cstring(path_to_str(dir)?)?;
+        // Number of files is an upper bound of number of plugins,
+        // so all loaded plugins fit into the array.
+        let max_count = std::fs::read_dir(dir)
+            .map_err(|err| {
+                HeifError::new(
+                    lh::heif_error_code_heif_error_Plugin_loading_error,
+                    lh::heif_suberror_code_heif_suberror_Cannot_read_plugin_directory,
+                    format!("cannot read directory {:?}: {}", dir, err),
+                )
+            })?
+            .count()
+            .min(libc::c_int::MAX as usize);
+        let mut c_infos = vec![ptr::null(); max_count.max(1)];
+        let mut count = 0;
+        let err = unsafe {
+            lh::heif_load_plugins(
+                c_dir.as_ptr(),
+                c_infos.as_mut_ptr(),
+                &mut count,
+                c_infos.len() as libc::c_int,
+            )
+        };
+        HeifError::from_heif_error(err)?;
+        c_infos.truncate(count.max(0) as usize);
+        Ok(c_infos
+            .into_iter()
+            .filter(|info| !info.is_null())
+            .map(|info| LoadedPlugin { info })
+            .collect())
+    }
+}
+
+impl Default for PluginRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn path_to_str(path: &Path) -> Result<&str> {
+    path.to_str().ok_or_else(|| {
+        HeifError::new(
+            lh::heif_error_code_heif_error_Usage_error,
+            lh::heif_suberror_code_heif_suberror_Unspecified,
+            format!("path {:?} is not valid UTF-8", path),
+        )
+    })
+}
+
+/// Plugin loaded by [`PluginRegistry`].
+///
+/// Encoders, decoders and images created by the plugin don't borrow it,
+/// so the plugin isn't unloaded on drop and stays loaded until
+/// the end of the process, unless [`LoadedPlugin::unload()`] is called.
+pub struct LoadedPlugin {
+    info: *const lh::heif_plugin_info,
+}
+
+impl LoadedPlugin {
+    /// `None` if the type isn't known by this version of the crate.
+    pub fn plugin_type(&self) -> Option<PluginType> {
+        PluginType::try_from(self.info().type_).ok()
+    }
+
+    /// Version of `heif_plugin_info` struct.
+    pub fn info_version(&self) -> i32 {
+        self.info().version
+    }
+
+    /// Raw pointer to the underlying `heif_plugin_info`.
+    pub fn as_ptr(&self) -> *const lh::heif_plugin_info {
+        self.info
+    }
+
+    /// Unload the plugin.
+    ///
+    /// # Safety
+    ///
+    /// No encoders, decoders or images created by the plugin may be alive,
+    /// and no contexts may use the plugin after this call.
+    pub unsafe fn unload(self) -> Result<()> {
+        HeifError::from_heif_error(lh::heif_unload_plugin(self.info))
+    }
+
+    fn info(&self) -> &lh::heif_plugin_info {
+        unsafe { &*self.info }
+    }
+}
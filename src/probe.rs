@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+
+use crate as lh;
+use crate::geometry::Transformation;
+use crate::{Brand, DisplayGeometry, HeifError, PixelAspectRatio, Result, Size};
+
+/// Information about a file read from its ISOBMFF boxes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeInfo {
+    /// Major brand from `ftyp` box.
+    pub main_brand: Brand,
+    pub minor_version: u32,
+    pub compatible_brands: Vec<Brand>,
+    /// Handler type from `meta/hdlr` box, `"pict"` for image files.
+    /// `None` if the data doesn't contain complete `meta` box.
+    pub handler_type: Option<String>,
+    /// ID from `pitm` box.
+    pub primary_item_id: Option<lh::heif_item_id>,
+    /// Items listed in `iinf` box.
+    pub items: Vec<ProbeItem>,
+    /// Size of the primary image from its `ispe` property,
+    /// before any transformations.
+    pub primary_size: Option<Size>,
+    /// References between items from `iref` box.
+    pub references: Vec<ProbeReference>,
+}
+
+/// Item listed in `iinf` box.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeItem {
+    pub id: lh::heif_item_id,
+    /// Type of the item, e.g. `"hvc1"`, `"av01"`, `"grid"` or `"Exif"`.
+    /// Empty for items described by `infe` boxes of version 0 and 1,
+    /// which don't contain the type.
+    pub item_type: String,
+    /// Geometry computed from properties of the item,
+    /// `None` if the item has no `ispe` property.
+    pub geometry: Option<DisplayGeometry>,
+}
+
+/// References of one type from an item to other items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeReference {
+    /// Type of the reference, e.g. `"dimg"`, `"thmb"`, `"auxl"` or `"cdsc"`.
+    pub reference_type: String,
+    pub from_item_id: lh::heif_item_id,
+    pub to_item_ids: Vec<lh::heif_item_id>,
+}
+
+/// Parse `ftyp` and `meta` boxes of a file.
+///
+/// The parser is implemented in pure Rust and doesn't call `libheif`.
+/// The data may be truncated, in which case only boxes
+/// which are completely contained in the data are parsed.
+pub fn probe(data: &[u8]) -> Result<ProbeInfo> {
+    let mut boxes = Boxes::new(data).take_complete();
+    let ftyp = match boxes.next().transpose()? {
+        Some(b) if b.box_type == *b"ftyp" => b,
+        _ => {
+            return Err(invalid_input(
+                lh::heif_suberror_code_heif_suberror_No_ftyp_box,
+                "data doesn't start with 'ftyp' box",
+            ))
+        }
+    };
+    let mut reader = Reader::new(ftyp.data);
+    let mut info = ProbeInfo {
+        main_brand: Brand::from(reader.u32()?),
+        minor_version: reader.u32()?,
+        compatible_brands: Vec::new(),
+        handler_type: None,
+        primary_item_id: None,
+        items: Vec::new(),
+        primary_size: None,
+        references: Vec::new(),
+    };
+    while !reader.is_empty() {
+        info.compatible_brands.push(Brand::from(reader.u32()?));
+    }
+
+    for b in boxes {
+        let b = b?;
+        if b.box_type == *b"meta" {
+            parse_meta(b.data, &mut info)?;
+            break;
+        }
+    }
+    Ok(info)
+}
+
+fn parse_meta(data: &[u8], info: &mut ProbeInfo) -> Result<()> {
+    let mut reader = Reader::new(data);
+    reader.full_box_header()?;
+    let mut properties = Vec::new();
+    let mut associations = HashMap::new();
+    for b in Boxes::new(reader.rest()) {
+        let b = b?;
+        let mut reader = Reader::new(b.data);
+        match &b.box_type {
+            b"hdlr" => {
+                reader.full_box_header()?;
+                reader.u32()?; // pre_defined
+                info.handler_type = Some(fourcc_to_string(reader.fourcc()?));
+            }
+            b"pitm" => {
+                let (version, _) = reader.full_box_header()?;
+                info.primary_item_id = Some(reader.item_id(version)?);
+            }
+            b"iinf" => {
+                let (version, _) = reader.full_box_header()?;
+                if version == 0 {
+                    reader.u16()?;
+                } else {
+                    reader.u32()?;
+                }
+                for infe in Boxes::new(reader.rest()) {
+                    info.items.push(parse_infe(infe?.data)?);
+                }
+            }
+            b"iref" => {
+                let (version, _) = reader.full_box_header()?;
+                for b in Boxes::new(reader.rest()) {
+                    let b = b?;
+                    let mut reader = Reader::new(b.data);
+                    let from_item_id = reader.item_id(version)?;
+                    let count = reader.u16()?;
+                    let to_item_ids = (0..count)
+                        .map(|_| reader.item_id(version))
+                        .collect::<Result<_>>()?;
+                    info.references.push(ProbeReference {
+                        reference_type: fourcc_to_string(b.box_type),
+                        from_item_id,
+                        to_item_ids,
+                    });
+                }
+            }
+            b"iprp" => {
+                for b in Boxes::new(b.data) {
+                    let b = b?;
+                    match &b.box_type {
+                        b"ipco" => {
+                            properties = Boxes::new(b.data).collect::<Result<Vec<_>>>()?;
+                        }
+                        b"ipma" => parse_ipma(b.data, &mut associations)?,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for item in &mut info.items {
+        let indexes = associations.get(&item.id).map(Vec::as_slice);
+        // Indexes of properties are 1-based, 0 means "no property".
+        let item_properties = indexes
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|&index| index.checked_sub(1).and_then(|i| properties.get(i)));
+        item.geometry = parse_geometry(item_properties)?;
+    }
+    info.primary_size = info
+        .primary_item_id
+        .and_then(|id| info.items.iter().find(|item| item.id == id))
+        .and_then(|item| item.geometry)
+        .map(|geometry| geometry.ispe);
+    Ok(())
+}
+
+fn parse_geometry<'a>(
+    properties: impl Iterator<Item = &'a IsoBox<'a>>,
+) -> Result<Option<DisplayGeometry>> {
+    let mut ispe = None;
+    let mut pixel_aspect_ratio = PixelAspectRatio::default();
+    let mut transformations = Vec::new();
+    for property in properties {
+        let mut reader = Reader::new(property.data);
+        match &property.box_type {
+            b"ispe" => {
+                reader.full_box_header()?;
+                ispe = Some(Size {
+                    width: reader.u32()?,
+                    height: reader.u32()?,
+                });
+            }
+            b"pasp" => {
+                pixel_aspect_ratio = PixelAspectRatio {
+                    h_spacing: reader.u32()?,
+                    v_spacing: reader.u32()?,
+                };
+            }
+            b"clap" => {
+                let width = fraction(reader.u32()?, reader.u32()?)?;
+                let height = fraction(reader.u32()?, reader.u32()?)?;
+                transformations.push(Transformation::CleanAperture(Size { width, height }));
+            }
+            b"irot" => transformations.push(Transformation::Rotation(reader.u8()? & 0x03)),
+            _ => {}
+        }
+    }
+    Ok(ispe.map(|ispe| DisplayGeometry::new(ispe, pixel_aspect_ratio, &transformations)))
+}
+
+/// Rounded value of a fraction from `clap` box.
+fn fraction(numerator: u32, denominator: u32) -> Result<u32> {
+    if denominator == 0 {
+        return Err(invalid_input(
+            lh::heif_suberror_code_heif_suberror_Invalid_clean_aperture,
+            "clean aperture has zero denominator",
+        ));
+    }
+    let (n, d) = (numerator as u64, denominator as u64);
+    Ok(((n + d / 2) / d) as u32)
+}
+
+/// Item type is stored in `infe` boxes of version 2 and later.
+fn parse_infe(data: &[u8]) -> Result<ProbeItem> {
+    let mut reader = Reader::new(data);
+    let (version, _) = reader.full_box_header()?;
+    let id = if version < 3 {
+        reader.u16()? as lh::heif_item_id
+    } else {
+        reader.u32()?
+    };
+    reader.u16()?; // item_protection_index
+    let item_type = if version < 2 {
+        String::new()
+    } else {
+        fourcc_to_string(reader.fourcc()?)
+    };
+    Ok(ProbeItem {
+        id,
+        item_type,
+        geometry: None,
+    })
+}
+
+fn parse_ipma(data: &[u8], associations: &mut HashMap<lh::heif_item_id, Vec<usize>>) -> Result<()> {
+    let mut reader = Reader::new(data);
+    let (version, flags) = reader.full_box_header()?;
+    let entry_count = reader.u32()?;
+    for _ in 0..entry_count {
+        let id = reader.item_id(version)?;
+        let count = reader.u8()?;
+        let mut indexes = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            // The highest bit is "essential" flag.
+            let index = if flags & 1 != 0 {
+                (reader.u16()? & 0x7fff) as usize
+            } else {
+                (reader.u8()? & 0x7f) as usize
+            };
+            indexes.push(index);
+        }
+        associations.entry(id).or_default().extend(indexes);
+    }
+    Ok(())
+}
+
+fn fourcc_to_string(fourcc: [u8; 4]) -> String {
+    String::from_utf8_lossy(&fourcc).into_owned()
+}
+
+fn invalid_input(sub_code: lh::heif_suberror_code, message: &str) -> HeifError {
+    HeifError::new(
+        lh::heif_error_code_heif_error_Invalid_input,
+        sub_code,
+        message,
+    )
+}
+
+fn end_of_data() -> HeifError {
+    invalid_input(
+        lh::heif_suberror_code_heif_suberror_End_of_data,
+        "unexpected end of box data",
+    )
+}
+
+/// Big-endian reader of box content.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        self.data
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        if self.data.len() < N {
+            return Err(end_of_data());
+        }
+        let (head, tail) = self.data.split_at(N);
+        self.data = tail;
+        let mut res = [0; N];
+        res.copy_from_slice(head);
+        Ok(res)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        self.bytes().map(u16::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        self.bytes().map(u32::from_be_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        self.bytes().map(u64::from_be_bytes)
+    }
+
+    fn fourcc(&mut self) -> Result<[u8; 4]> {
+        self.bytes()
+    }
+
+    /// Version and flags of a full box.
+    fn full_box_header(&mut self) -> Result<(u8, u32)> {
+        let value = self.u32()?;
+        Ok(((value >> 24) as u8, value & 0x00ff_ffff))
+    }
+
+    /// Item IDs are 16-bit in boxes of version 0.
+    fn item_id(&mut self, version: u8) -> Result<lh::heif_item_id> {
+        if version == 0 {
+            Ok(self.u16()? as lh::heif_item_id)
+        } else {
+            self.u32()
+        }
+    }
+}
+
+struct IsoBox<'a> {
+    box_type: [u8; 4],
+    data: &'a [u8],
+}
+
+/// Iterator over sequence of boxes.
+struct Boxes<'a> {
+    data: &'a [u8],
+    /// Stop at the first truncated box instead of returning an error.
+    stop_at_truncated: bool,
+}
+
+impl<'a> Boxes<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            stop_at_truncated: false,
+        }
+    }
+
+    fn take_complete(mut self) -> Self {
+        self.stop_at_truncated = true;
+        self
+    }
+
+    /// Type, header size and total size of the next box.
+    fn header(&self) -> Result<([u8; 4], u64, u64)> {
+        let mut reader = Reader::new(self.data);
+        let size = reader.u32()? as u64;
+        let box_type = reader.fourcc()?;
+        Ok(match size {
+            0 => (box_type, 8, self.data.len() as u64),
+            1 => (box_type, 16, reader.u64()?),
+            _ => (box_type, 8, size),
+        })
+    }
+
+    fn next_box(&mut self) -> Result<Option<IsoBox<'a>>> {
+        let (box_type, header_size, size) = match self.header() {
+            Err(_) if self.stop_at_truncated => return Ok(None),
+            res => res?,
+        };
+        if size < header_size {
+            return Err(invalid_input(
+                lh::heif_suberror_code_heif_suberror_Invalid_box_size,
+                "box size is smaller than its header",
+            ));
+        }
+        if size > self.data.len() as u64 {
+            if self.stop_at_truncated {
+                return Ok(None);
+            }
+            return Err(invalid_input(
+                lh::heif_suberror_code_heif_suberror_Invalid_box_size,
+                "box size exceeds size of its parent",
+            ));
+        }
+        let (content, tail) = self.data.split_at(size as usize);
+        self.data = tail;
+        Ok(Some(IsoBox {
+            box_type,
+            data: &content[header_size as usize..],
+        }))
+    }
+}
+
+impl<'a> Iterator for Boxes<'a> {
+    type Item = Result<IsoBox<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let res = self.next_box();
+        if !matches!(res, Ok(Some(_))) {
+            // Don't parse anything after a broken box.
+            self.data = &[];
+        }
+        res.transpose()
+    }
+}
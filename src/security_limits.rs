@@ -0,0 +1,233 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::Path;
+
+use crate as lh;
+use crate::utils::to_c_int;
+use crate::{probe, HeifContext, HeifError, ImageHandle, Result};
+
+/// Limits applied while reading of files from untrusted sources.
+///
+/// Image size and number of decoding threads are always limited by `libheif`.
+/// If the crate is built with bindings of `libheif` 1.19 or later,
+/// number of items and size of ICC profiles are also passed into
+/// `heif_security_limits` of the context. In any case all limits
+/// except the number of decoding threads are checked by the crate itself
+/// before the context is returned, so with older versions of `libheif`
+/// they are enforced only after the file is parsed by the library.
+/// `None` means that a value isn't limited, except for the default
+/// limits of `libheif` itself.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct SecurityLimits {
+    /// Maximal width and height of images.
+    pub max_image_size: Option<u32>,
+    /// Maximal number of threads used to decode tiles of grid images.
+    pub max_decoding_threads: Option<u32>,
+    /// Maximal number of items listed in `iinf` box.
+    pub max_items: Option<usize>,
+    /// Maximal size of every metadata block attached to top-level images.
+    pub max_metadata_size: Option<usize>,
+    /// Maximal size of ICC profiles of top-level images.
+    pub max_icc_size: Option<usize>,
+    /// Maximal length of chains of references between items,
+    /// e.g. a thumbnail referencing a grid image which references its tiles.
+    pub max_iref_depth: Option<usize>,
+}
+
+impl SecurityLimits {
+    /// Conservative limits for decoding of user uploads.
+    pub fn untrusted() -> Self {
+        Self {
+            max_image_size: Some(16384),
+            max_decoding_threads: Some(4),
+            max_items: Some(1000),
+            max_metadata_size: Some(4 * 1024 * 1024),
+            max_icc_size: Some(4 * 1024 * 1024),
+            max_iref_depth: Some(4),
+        }
+    }
+
+    /// Check limits which require parsing of boxes of the file.
+    fn check_structure(&self, bytes: &[u8]) -> Result<()> {
+        if self.max_items.is_none() && self.max_iref_depth.is_none() {
+            return Ok(());
+        }
+        let info = probe(bytes)?;
+        if let Some(max_items) = self.max_items {
+            check_limit("number of items", info.items.len(), max_items)?;
+        }
+        if let Some(max_depth) = self.max_iref_depth {
+            let mut references: HashMap<lh::heif_item_id, Vec<lh::heif_item_id>> = HashMap::new();
+            for reference in &info.references {
+                references
+                    .entry(reference.from_item_id)
+                    .or_default()
+                    .extend(&reference.to_item_ids);
+            }
+            check_iref_depth(&references, max_depth)?;
+        }
+        Ok(())
+    }
+
+    fn apply(&self, ctx: &mut HeifContext) -> Result<()> {
+        if let Some(size) = self.max_image_size {
+            let size = to_c_int(size)?;
+            unsafe { lh::heif_context_set_maximum_image_size_limit(ctx.inner, size) };
+        }
+        if let Some(threads) = self.max_decoding_threads {
+            let threads = to_c_int(threads)?;
+            unsafe { lh::heif_context_set_max_decoding_threads(ctx.inner, threads) };
+        }
+        #[cfg(libheif_security_limits)]
+        self.apply_native(ctx);
+        Ok(())
+    }
+
+    /// Copy limits into `heif_security_limits` of the context.
+    #[cfg(libheif_security_limits)]
+    fn apply_native(&self, ctx: &mut HeifContext) {
+        use std::convert::TryFrom;
+
+        let limits = unsafe { lh::heif_context_get_security_limits(ctx.inner) };
+        let limits = match unsafe { limits.as_mut() } {
+            Some(limits) => limits,
+            None => return,
+        };
+        let to_u32 = |value: usize| u32::try_from(value).unwrap_or(u32::MAX);
+        if let Some(size) = self.max_image_size {
+            limits.max_image_size_pixels = size as u64 * size as u64;
+        }
+        if let Some(max_items) = self.max_items {
+            limits.max_items = to_u32(max_items);
+        }
+        if let Some(max_size) = self.max_icc_size {
+            limits.max_color_profile_size = to_u32(max_size);
+        }
+    }
+
+    /// Check sizes of metadata and color profiles of top-level images.
+    fn check_content(&self, ctx: &HeifContext) -> Result<()> {
+        if self.max_metadata_size.is_none() && self.max_icc_size.is_none() {
+            return Ok(());
+        }
+        for image in ctx.images() {
+            self.check_image(&image?.handle)?;
+        }
+        Ok(())
+    }
+
+    fn check_image(&self, handle: &ImageHandle) -> Result<()> {
+        if let Some(max_size) = self.max_metadata_size {
+            for id in handle.metadata_ids(std::ptr::null()) {
+                let size = unsafe { lh::heif_image_handle_get_metadata_size(handle.inner, id) };
+                check_limit("size of metadata block", size, max_size)?;
+            }
+        }
+        if let Some(max_size) = self.max_icc_size {
+            let size = unsafe { lh::heif_image_handle_get_raw_color_profile_size(handle.inner) };
+            check_limit("size of ICC profile", size, max_size)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check lengths of chains of references without recursion,
+/// so that long chains can't overflow the stack.
+/// Circular references are errors regardless of the limit.
+fn check_iref_depth(
+    references: &HashMap<lh::heif_item_id, Vec<lh::heif_item_id>>,
+    max_depth: usize,
+) -> Result<()> {
+    let name = "depth of item references";
+    // Number of references in the longest chain started from an item.
+    let mut depths: HashMap<lh::heif_item_id, usize> = HashMap::new();
+    let mut visiting: HashSet<lh::heif_item_id> = HashSet::new();
+    for &start_id in references.keys() {
+        if depths.contains_key(&start_id) {
+            continue;
+        }
+        // Items of the current chain with indexes of their next references.
+        let mut stack = vec![(start_id, 0)];
+        visiting.insert(start_id);
+        while let Some((id, next)) = stack.last_mut() {
+            let id = *id;
+            let to_ids = references.get(&id).map(Vec::as_slice).unwrap_or_default();
+            if let Some(&to_id) = to_ids.get(*next) {
+                *next += 1;
+                if visiting.contains(&to_id) {
+                    return Err(limit_exceeded(format!(
+                        "circular reference to item {}",
+                        to_id
+                    )));
+                }
+                match depths.get(&to_id) {
+                    Some(&depth) => check_limit(name, stack.len() + depth, max_depth)?,
+                    None => {
+                        check_limit(name, stack.len(), max_depth)?;
+                        visiting.insert(to_id);
+                        stack.push((to_id, 0));
+                    }
+                }
+            } else {
+                let depth = to_ids.iter().map(|to_id| depths[to_id] + 1).max();
+                depths.insert(id, depth.unwrap_or(0));
+                visiting.remove(&id);
+                stack.pop();
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_limit(name: &str, value: usize, limit: usize) -> Result<()> {
+    if value > limit {
+        return Err(limit_exceeded(format!(
+            "{} {} exceeds security limit {}",
+            name, value, limit
+        )));
+    }
+    Ok(())
+}
+
+fn limit_exceeded(message: String) -> HeifError {
+    HeifError::new(
+        lh::heif_error_code_heif_error_Memory_allocation_error,
+        lh::heif_suberror_code_heif_suberror_Security_limit_exceeded,
+        message,
+    )
+}
+
+impl HeifContext {
+    /// Create a new context from bytes of HEIF file with security limits.
+    /// The data is copied into the context.
+    pub fn read_from_bytes_with_limits(bytes: &[u8], limits: &SecurityLimits) -> Result<Self> {
+        limits.check_structure(bytes)?;
+        let mut ctx = Self::new()?;
+        limits.apply(&mut ctx)?;
+        ctx.read_bytes(bytes)?;
+        limits.check_content(&ctx)?;
+        Ok(ctx)
+    }
+
+    /// Create a new context from HEIF file with security limits.
+    /// The whole file is read into memory.
+    pub fn read_from_file_with_limits<P: AsRef<Path>>(
+        path: P,
+        limits: &SecurityLimits,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|err| {
+            let code = if err.kind() == io::ErrorKind::NotFound {
+                lh::heif_error_code_heif_error_Input_does_not_exist
+            } else {
+                lh::heif_error_code_heif_error_Invalid_input
+            };
+            HeifError::new(
+                code,
+                lh::heif_suberror_code_heif_suberror_Unspecified,
+                format!("cannot read file {:?}: {}", path, err),
+            )
+        })?;
+        Self::read_from_bytes_with_limits(&bytes, limits)
+    }
+}
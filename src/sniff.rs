@@ -0,0 +1,124 @@
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use std::fmt;
+use std::ptr;
+use std::slice;
+
+use crate as lh;
+use crate::enums::FileTypeResult;
+
+/// Brand of a file stored as FourCC code in `ftyp` box.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Brand(lh::heif_brand2);
+
+impl Brand {
+    /// Brand returned for data which don't contain `ftyp` box.
+    pub const UNKNOWN: Brand = Brand(0);
+    /// HEIF image with h265.
+    pub const HEIC: Brand = Brand::from_bytes(*b"heic");
+    /// 10bit images, or anything that uses h265 with range extension.
+    pub const HEIX: Brand = Brand::from_bytes(*b"heix");
+    /// HEIF image sequence with h265.
+    pub const HEVC: Brand = Brand::from_bytes(*b"hevc");
+    pub const HEVX: Brand = Brand::from_bytes(*b"hevx");
+    /// Image, any coding algorithm.
+    pub const MIF1: Brand = Brand::from_bytes(*b"mif1");
+    /// Sequence, any coding algorithm.
+    pub const MSF1: Brand = Brand::from_bytes(*b"msf1");
+    /// HEIF image with AV1.
+    pub const AVIF: Brand = Brand::from_bytes(*b"avif");
+    /// HEIF image sequence with AV1.
+    pub const AVIS: Brand = Brand::from_bytes(*b"avis");
+    pub const MIAF: Brand = Brand::from_bytes(*b"miaf");
+    /// JPEG2000 image.
+    pub const J2KI: Brand = Brand::from_bytes(*b"j2ki");
+
+    const fn from_bytes(fourcc: [u8; 4]) -> Self {
+        Self(u32::from_be_bytes(fourcc))
+    }
+
+    pub fn from_fourcc(fourcc: [u8; 4]) -> Self {
+        Self::from_bytes(fourcc)
+    }
+
+    pub fn fourcc(self) -> [u8; 4] {
+        self.0.to_be_bytes()
+    }
+}
+
+impl From<lh::heif_brand2> for Brand {
+    fn from(value: lh::heif_brand2) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Brand> for lh::heif_brand2 {
+    fn from(brand: Brand) -> Self {
+        brand.0
+    }
+}
+
+impl fmt::Display for Brand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.fourcc()))
+    }
+}
+
+impl fmt::Debug for Brand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Brand({:?})", self.to_string())
+    }
+}
+
+/// Result of detection of file type by its first bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sniff {
+    pub filetype: FileTypeResult,
+    /// Major brand from `ftyp` box, [`Brand::UNKNOWN`] if there is no such box.
+    pub main_brand: Brand,
+    pub compatible_brands: Vec<Brand>,
+    /// MIME type of the file, `None` if the format is not detected.
+    pub mime: Option<&'static str>,
+    pub is_jpeg: bool,
+}
+
+/// Detect type of a file by its first bytes.
+///
+/// At least 12 bytes are required to detect HEIF file, and all bytes
+/// of `ftyp` box (usually about 50 bytes) to get its compatible brands.
+pub fn sniff(data: &[u8]) -> Sniff {
+    let ptr = data.as_ptr();
+    let len = libc::c_int::try_from(data.len()).unwrap_or(libc::c_int::MAX);
+    let filetype = unsafe { lh::heif_check_filetype(ptr, len) };
+
+    let mut compatible_brands = Vec::new();
+    let mut c_brands = ptr::null_mut();
+    let mut count = 0;
+    let err = unsafe { lh::heif_list_compatible_brands(ptr, len, &mut c_brands, &mut count) };
+    if err.code == lh::heif_error_code_heif_error_Ok && !c_brands.is_null() {
+        unsafe {
+            let brands = slice::from_raw_parts(c_brands, count.max(0) as usize);
+            compatible_brands.extend(brands.iter().map(|&b| Brand(b)));
+            lh::heif_free_list_of_compatible_brands(c_brands);
+        }
+    }
+
+    let c_mime = unsafe { lh::heif_get_file_mime_type(ptr, len) };
+    // `libheif` returns pointers to string literals.
+    let mime = if c_mime.is_null() {
+        None
+    } else {
+        unsafe { CStr::from_ptr::<'static>(c_mime) }
+            .to_str()
+            .ok()
+            .filter(|m| !m.is_empty())
+    };
+
+    Sniff {
+        filetype: FileTypeResult::try_from(filetype).unwrap_or(FileTypeResult::No),
+        main_brand: Brand(unsafe { lh::heif_read_main_brand(ptr, len) }),
+        compatible_brands,
+        mime,
+        is_jpeg: unsafe { lh::heif_check_jpeg_filetype(ptr, len) } != 0,
+    }
+}
@@ -0,0 +1,155 @@
+use std::ptr;
+
+use crate as lh;
+use crate::utils::{item_ids, to_c_int};
+use crate::{
+    Chroma, ColorSpace, Encoder, EncodingOptions, HeifContext, HeifError, Image, ImageHandle,
+    Result,
+};
+
+/// Iterator over thumbnails of an image handle.
+pub struct Thumbnails<'a> {
+    handle: &'a ImageHandle,
+    ids: std::vec::IntoIter<lh::heif_item_id>,
+}
+
+impl<'a> Iterator for Thumbnails<'a> {
+    type Item = Result<ImageHandle>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.ids.next()?;
+        Some(self.handle.thumbnail(id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+impl ImageHandle {
+    /// Iterate over thumbnails attached to the image.
+    pub fn thumbnails(&self) -> Thumbnails<'_> {
+        let count = unsafe { lh::heif_image_handle_get_number_of_thumbnails(self.inner) };
+        let ids = item_ids(count, |ids, count| unsafe {
+            lh::heif_image_handle_get_list_of_thumbnail_IDs(self.inner, ids, count)
+        });
+        Thumbnails {
+            handle: self,
+            ids: ids.into_iter(),
+        }
+    }
+
+    fn thumbnail(&self, thumbnail_id: lh::heif_item_id) -> Result<ImageHandle> {
+        let mut c_handle = ptr::null_mut();
+        let err =
+            unsafe { lh::heif_image_handle_get_thumbnail(self.inner, thumbnail_id, &mut c_handle) };
+        HeifError::from_heif_error(err)?;
+        Ok(ImageHandle { inner: c_handle })
+    }
+
+    /// Decode an image which fits into a square area of width `bbox_size`
+    /// for displaying it in place of this image.
+    ///
+    /// The smallest embedded thumbnail which isn't smaller than `bbox_size`
+    /// is used. If there is no such thumbnail, the image itself is decoded.
+    /// The decoded image is scaled down to fit into the area if required.
+    pub fn best_thumbnail(
+        &self,
+        bbox_size: u32,
+        color_space: ColorSpace,
+        chroma: Chroma,
+    ) -> Result<Image> {
+        check_bbox_size(bbox_size)?;
+        let mut best: Option<ImageHandle> = None;
+        for thumbnail in self.thumbnails() {
+            let thumbnail = thumbnail?;
+            let size = longest_side(&thumbnail);
+            if size < bbox_size {
+                continue;
+            }
+            match best {
+                Some(ref b) if longest_side(b) <= size => {}
+                _ => best = Some(thumbnail),
+            }
+        }
+        let image = best.as_ref().unwrap_or(self).decode(color_space, chroma)?;
+
+        let (width, height) = (image.width(), image.height());
+        if width.max(height) <= bbox_size {
+            return Ok(image);
+        }
+        let fit = |value: u32, longest: u32| {
+            ((value as u64 * bbox_size as u64 + longest as u64 / 2) / longest as u64).max(1) as u32
+        };
+        if width >= height {
+            image.scale(bbox_size, fit(height, width))
+        } else {
+            image.scale(fit(width, height), bbox_size)
+        }
+    }
+}
+
+fn longest_side(handle: &ImageHandle) -> u32 {
+    handle.width().max(handle.height())
+}
+
+fn check_bbox_size(bbox_size: u32) -> Result<()> {
+    if bbox_size == 0 {
+        return Err(HeifError::new(
+            lh::heif_error_code_heif_error_Usage_error,
+            lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+            "size of bounding box of thumbnail must be positive",
+        ));
+    }
+    Ok(())
+}
+
+impl HeifContext {
+    /// Encode a scaled down copy of the `image` as thumbnail
+    /// of the `master` image, which was encoded from the same `image`.
+    ///
+    /// The thumbnail fits into a square area of width `bbox_size`.
+    /// Returns `None` if the image already fits into this area,
+    /// in which case no thumbnail is added.
+    pub fn add_thumbnail(
+        &mut self,
+        master: &ImageHandle,
+        image: &Image,
+        bbox_size: u32,
+        encoder: &mut Encoder,
+        options: &EncodingOptions,
+    ) -> Result<Option<ImageHandle>> {
+        check_bbox_size(bbox_size)?;
+        if image.width().max(image.height()) <= bbox_size {
+            return Ok(None);
+        }
+        let mut c_handle = ptr::null_mut();
+        let err = unsafe {
+            lh::heif_context_encode_thumbnail(
+                self.inner,
+                image.inner,
+                master.inner,
+                encoder.inner,
+                options.as_ptr()?,
+                to_c_int(bbox_size)?,
+                &mut c_handle,
+            )
+        };
+        HeifError::from_heif_error(err)?;
+        if c_handle.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(ImageHandle { inner: c_handle }))
+    }
+
+    /// Assign an already encoded image as thumbnail of the `master` image.
+    pub fn assign_thumbnail(
+        &mut self,
+        master: &ImageHandle,
+        thumbnail: &ImageHandle,
+    ) -> Result<()> {
+        let err =
+            unsafe { lh::heif_context_assign_thumbnail(self.inner, master.inner, thumbnail.inner) };
+        HeifError::from_heif_error(err)
+    }
+}
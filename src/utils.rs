@@ -0,0 +1,71 @@
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::ptr;
+use std::sync::Once;
+
+use crate as lh;
+use crate::{HeifError, Result};
+
+static INIT: Once = Once::new();
+
+/// Initialises `libheif` once per process. Required for loading of plugins
+/// before the first context or encoder is created.
+pub(crate) fn ensure_initialized() {
+    INIT.call_once(|| unsafe {
+        lh::heif_init(ptr::null_mut());
+    });
+}
+
+/// Copies a C-string returned by `libheif` into an owned `String`.
+pub(crate) fn cstr_to_string(c_str: *const libc::c_char) -> Option<String> {
+    if c_str.is_null() {
+        return None;
+    }
+    let res = unsafe { CStr::from_ptr(c_str) };
+    Some(res.to_string_lossy().into_owned())
+}
+
+pub(crate) fn str_to_cstring(value: &str) -> Result<CString> {
+    CString::new(value).map_err(|_| {
+        HeifError::new(
+            lh::heif_error_code_heif_error_Usage_error,
+            lh::heif_suberror_code_heif_suberror_Unspecified,
+            format!("string {:?} contains a nul byte", value),
+        )
+    })
+}
+
+pub(crate) fn to_c_int(value: u32) -> Result<libc::c_int> {
+    libc::c_int::try_from(value).map_err(|_| {
+        HeifError::new(
+            lh::heif_error_code_heif_error_Usage_error,
+            lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+            format!("value {} is too big", value),
+        )
+    })
+}
+
+pub(crate) fn len_to_c_int(len: usize) -> Result<libc::c_int> {
+    libc::c_int::try_from(len).map_err(|_| {
+        HeifError::new(
+            lh::heif_error_code_heif_error_Usage_error,
+            lh::heif_suberror_code_heif_suberror_Invalid_parameter_value,
+            format!("size {} of data is too big", len),
+        )
+    })
+}
+
+/// Collects IDs of items with help of pair of `libheif` functions
+/// that return number of items and fill a list of their IDs.
+pub(crate) fn item_ids(
+    count: libc::c_int,
+    fill_ids: impl FnOnce(*mut lh::heif_item_id, libc::c_int) -> libc::c_int,
+) -> Vec<lh::heif_item_id> {
+    if count <= 0 {
+        return Vec::new();
+    }
+    let mut ids = vec![0; count as usize];
+    let count = fill_ids(ids.as_mut_ptr(), count);
+    ids.truncate(count.max(0) as usize);
+    ids
+}
@@ -0,0 +1,105 @@
+use crate as lh;
+use crate::{HeifError, Result};
+
+const GPS_PREFIX: &[u8] = b"exif:GPS";
+
+/// Removes `exif:GPS*` properties from XMP packet.
+///
+/// Properties are removed both in attribute form
+/// (`exif:GPSLatitude="..."`) and in element form
+/// (`<exif:GPSLatitude>...</exif:GPSLatitude>`), other content
+/// of the packet is kept as is. Returns an error if a GPS property
+/// can't be parsed, so that it is never copied by mistake.
+pub(crate) fn strip_gps(data: &[u8]) -> Result<Vec<u8>> {
+    let mut res = Vec::with_capacity(data.len());
+    let mut pos = 0;
+    while let Some(offset) = find(&data[pos..], GPS_PREFIX) {
+        let start = pos + offset;
+        let removed = match start.checked_sub(1).map(|i| data[i]) {
+            Some(b'<') => Some((start - 1, element_end(data, start)?)),
+            Some(c) if c.is_ascii_whitespace() => {
+                let ws_start = data[..start]
+                    .iter()
+                    .rposition(|c| !c.is_ascii_whitespace())
+                    .map_or(0, |i| i + 1);
+                Some((ws_start, attribute_end(data, start)?))
+            }
+            // E.g. a text which mentions the property.
+            _ => None,
+        };
+        match removed {
+            Some((removed_start, removed_end)) => {
+                res.extend_from_slice(&data[pos..removed_start]);
+                pos = removed_end;
+            }
+            None => {
+                let end = start + GPS_PREFIX.len();
+                res.extend_from_slice(&data[pos..end]);
+                pos = end;
+            }
+        }
+    }
+    res.extend_from_slice(&data[pos..]);
+    Ok(res)
+}
+
+/// End of element which name starts at `start`.
+fn element_end(data: &[u8], start: usize) -> Result<usize> {
+    let name_end = name_end(data, start);
+    let tag_end = find(&data[name_end..], b">")
+        .map(|i| name_end + i + 1)
+        .ok_or_else(malformed)?;
+    if data[tag_end - 2] == b'/' {
+        return Ok(tag_end);
+    }
+    let mut closing_tag = b"</".to_vec();
+    closing_tag.extend_from_slice(&data[start..name_end]);
+    closing_tag.push(b'>');
+    find(&data[tag_end..], &closing_tag)
+        .map(|i| tag_end + i + closing_tag.len())
+        .ok_or_else(malformed)
+}
+
+/// End of attribute which name starts at `start`.
+fn attribute_end(data: &[u8], start: usize) -> Result<usize> {
+    let mut pos = skip_whitespace(data, name_end(data, start));
+    if data.get(pos) != Some(&b'=') {
+        return Err(malformed());
+    }
+    pos = skip_whitespace(data, pos + 1);
+    let quote = match data.get(pos) {
+        Some(&quote) if quote == b'"' || quote == b'\'' => quote,
+        _ => return Err(malformed()),
+    };
+    data[pos + 1..]
+        .iter()
+        .position(|&c| c == quote)
+        .map(|i| pos + 1 + i + 1)
+        .ok_or_else(malformed)
+}
+
+fn name_end(data: &[u8], start: usize) -> usize {
+    data[start..]
+        .iter()
+        .position(|&c| !(c.is_ascii_alphanumeric() || b":_-.".contains(&c)))
+        .map_or(data.len(), |i| start + i)
+}
+
+fn skip_whitespace(data: &[u8], start: usize) -> usize {
+    data[start..]
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .map_or(data.len(), |i| start + i)
+}
+
+fn find(data: &[u8], pattern: &[u8]) -> Option<usize> {
+    data.windows(pattern.len()).position(|w| w == pattern)
+}
+
+fn malformed() -> HeifError {
+    HeifError::new(
+        lh::heif_error_code_heif_error_Invalid_input,
+        lh::heif_suberror_code_heif_suberror_Unspecified,
+        "XMP data contains malformed GPS property",
+    )
+}
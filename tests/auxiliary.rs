@@ -0,0 +1,42 @@
+use libheif_sys::{encode_rgba_to_heic, AuxiliaryKind, HeifContext};
+
+#[test]
+fn detect_auxiliary_kind() {
+    assert_eq!(
+        AuxiliaryKind::from_urn("urn:mpeg:hevc:2015:auxid:1"),
+        AuxiliaryKind::Alpha
+    );
+    assert_eq!(
+        AuxiliaryKind::from_urn("urn:mpeg:mpegB:cicp:systems:auxiliary:depth"),
+        AuxiliaryKind::Depth
+    );
+    assert_eq!(
+        AuxiliaryKind::from_urn("urn:com:apple:photo:2020:aux:hdrgainmap"),
+        AuxiliaryKind::HdrGainMap
+    );
+    assert_eq!(
+        AuxiliaryKind::from_urn("urn:com:apple:photo:2019:aux:semanticskinmatte"),
+        AuxiliaryKind::SemanticMatte("skin".into())
+    );
+    assert_eq!(
+        AuxiliaryKind::from_urn("urn:example:aux"),
+        AuxiliaryKind::Other("urn:example:aux".into())
+    );
+}
+
+#[test]
+fn alpha_auxiliary_image() {
+    let pixels: Vec<u8> = (0..16 * 16 * 4).map(|i| i as u8).collect();
+    let data = encode_rgba_to_heic(&pixels, 16, 16, &Default::default()).unwrap();
+    let ctx = HeifContext::read_from_bytes(&data).unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+
+    assert_eq!(handle.auxiliary_images(false).count(), 0);
+    let images: Vec<_> = handle
+        .auxiliary_images(true)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].kind, AuxiliaryKind::Alpha);
+    assert_eq!(images[0].handle.width(), 16);
+}
@@ -0,0 +1,54 @@
+use libheif_sys as lh;
+use libheif_sys::{capabilities, encoder_descriptors, CompressionFormat};
+
+#[test]
+fn capabilities_report() {
+    let report = capabilities().unwrap();
+    assert_eq!(report.version_number, unsafe {
+        lh::heif_get_version_number()
+    });
+    assert!(report.version.starts_with(&format!("{}.", unsafe {
+        lh::heif_get_version_number_major()
+    })));
+    assert_eq!(
+        report.encoders.len(),
+        encoder_descriptors(None, None).unwrap().len()
+    );
+    assert!(report.can_decode(CompressionFormat::Hevc));
+    assert!(report.can_encode(CompressionFormat::Hevc));
+    assert!(!report.can_decode(CompressionFormat::Undefined));
+    let hevc_encoder = report
+        .encoders
+        .iter()
+        .find(|e| e.format == Some(CompressionFormat::Hevc))
+        .unwrap();
+    assert!(hevc_encoder.lossy);
+}
+
+#[test]
+fn capabilities_table() {
+    let report = capabilities().unwrap();
+    let table = report.to_string();
+    let mut lines = table.lines();
+    assert_eq!(
+        lines.next(),
+        Some(format!("libheif {}", report.version).as_str())
+    );
+    assert_eq!(lines.next(), Some("Decoders:"));
+    assert!(lines.next().unwrap().trim_start().starts_with("format"));
+    for decoder in &report.decoders {
+        assert!(table.contains(&decoder.name));
+    }
+    assert!(table.contains("Encoders:"));
+    assert_eq!(
+        table.lines().count(),
+        report.decoders.len() + report.encoders.len() + 5
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn capabilities_are_serializable() {
+    fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>(_: &T) {}
+    assert_serde(&capabilities().unwrap());
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use libheif_sys::{
+    ColorPrimaries, ColorProfile, HeifContext, MatrixCoefficients, NclxProfile,
+    TransferCharacteristics,
+};
+
+use common::{create_rgb_image, encode_primary, write_and_read};
+
+fn srgb_profile() -> NclxProfile {
+    NclxProfile::new(
+        ColorPrimaries::Bt709,
+        TransferCharacteristics::Iec61966_2_1,
+        MatrixCoefficients::Bt601,
+        true,
+    )
+}
+
+#[test]
+fn set_and_get_image_color_profiles() {
+    let mut image = create_rgb_image(16, 16);
+    assert_eq!(image.color_profile().unwrap(), None);
+
+    let nclx = ColorProfile::Nclx(srgb_profile());
+    image.set_color_profile(nclx.clone()).unwrap();
+    assert_eq!(image.color_profile().unwrap(), Some(nclx));
+
+    let icc = icc_profile(false);
+    image.set_color_profile(icc.clone()).unwrap();
+    assert_eq!(image.color_profile().unwrap(), Some(icc));
+}
+
+fn icc_profile(restricted: bool) -> ColorProfile {
+    ColorProfile::Icc {
+        restricted,
+        data: b"fake ICC profile".to_vec(),
+    }
+}
+
+#[test]
+fn read_color_profile_of_encoded_image() {
+    for restricted in [false, true] {
+        let mut image = create_rgb_image(16, 16);
+        let profile = icc_profile(restricted);
+        image.set_color_profile(profile.clone()).unwrap();
+
+        let mut ctx = HeifContext::new().unwrap();
+        encode_primary(&mut ctx, &image);
+
+        let ctx = write_and_read(&ctx);
+        let handle = ctx.primary_image_handle().unwrap();
+        assert_eq!(handle.color_profile().unwrap(), Some(profile));
+    }
+}
+
+#[test]
+fn convert_nclx_values() {
+    use libheif_sys as lh;
+
+    assert_eq!(
+        ColorPrimaries::from(lh::heif_color_primaries_heif_color_primaries_SMPTE_EG_432_1),
+        ColorPrimaries::SmpteEg432
+    );
+    assert_eq!(ColorPrimaries::from(3), ColorPrimaries::Unknown(3));
+    assert_eq!(
+        lh::heif_color_primaries::from(ColorPrimaries::Unknown(3)),
+        3
+    );
+    assert_eq!(
+        lh::heif_transfer_characteristics::from(TransferCharacteristics::Bt2100Pq),
+        16
+    );
+    assert_eq!(
+        MatrixCoefficients::from(lh::heif_matrix_coefficients_heif_matrix_coefficients_ICtCp),
+        MatrixCoefficients::ICtCp
+    );
+}
+
+#[test]
+fn unsupported_nclx_value_is_rejected() {
+    let mut image = create_rgb_image(16, 16);
+    let mut profile = srgb_profile();
+    profile.color_primaries = ColorPrimaries::Unknown(1000);
+    assert!(image
+        .set_color_profile(ColorProfile::Nclx(profile))
+        .is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn nclx_enums_are_serializable() {
+    fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>(_: &T) {}
+    assert_serde(&ColorPrimaries::Unknown(3));
+    assert_serde(&TransferCharacteristics::Bt2100Pq);
+    assert_serde(&MatrixCoefficients::Bt601);
+}
@@ -0,0 +1,56 @@
+// Every test crate uses only a part of these helpers.
+#![allow(dead_code)]
+
+use libheif_sys::{
+    Channel, Chroma, ColorSpace, CompressionFormat, EncodingOptions, HeifContext, Image,
+    ImageHandle,
+};
+
+/// RGB image with a single interleaved plane of the given bit depth.
+pub fn create_image(width: u32, height: u32, chroma: Chroma, bit_depth: u8) -> Image {
+    let mut image = Image::new(width, height, ColorSpace::Rgb, chroma).unwrap();
+    image
+        .add_plane(Channel::Interleaved, width, height, bit_depth)
+        .unwrap();
+    image
+}
+
+/// 8-bit RGB image filled with a gradient.
+pub fn create_rgb_image(width: u32, height: u32) -> Image {
+    let mut image = create_image(width, height, Chroma::InterleavedRgb, 8);
+    let plane = image.plane_mut(Channel::Interleaved).unwrap();
+    for (y, row) in plane.data.chunks_exact_mut(plane.stride).enumerate() {
+        for (x, pixel) in row[..width as usize * 3].chunks_exact_mut(3).enumerate() {
+            pixel.copy_from_slice(&[x as u8, y as u8, 128]);
+        }
+    }
+    image
+}
+
+/// Encode the image into HEVC with default options
+/// and make it the primary image of the context.
+pub fn encode_primary(ctx: &mut HeifContext, image: &Image) -> ImageHandle {
+    encode_primary_with_options(ctx, image, &EncodingOptions::new().unwrap())
+}
+
+pub fn encode_primary_with_options(
+    ctx: &mut HeifContext,
+    image: &Image,
+    options: &EncodingOptions,
+) -> ImageHandle {
+    let mut encoder = ctx.encoder_for_format(CompressionFormat::Hevc).unwrap();
+    let handle = ctx.encode_image(image, &mut encoder, options).unwrap();
+    ctx.set_primary_image(&handle).unwrap();
+    handle
+}
+
+/// Write the context into bytes and read them into a new context.
+pub fn write_and_read(ctx: &HeifContext) -> HeifContext {
+    HeifContext::read_from_bytes(&ctx.write_to_bytes().unwrap()).unwrap()
+}
+
+/// ISOBMFF box with the given type and content.
+pub fn iso_box(box_type: &[u8; 4], content: &[u8]) -> Vec<u8> {
+    let size = (content.len() + 8) as u32;
+    [&size.to_be_bytes()[..], box_type, content].concat()
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use libheif_sys as lh;
+use libheif_sys::{CollectionImage, EncodeSettings, HeifContext, HeifContextBuilder, MetadataKind};
+
+use common::create_rgb_image;
+
+#[test]
+fn write_collection() {
+    let frames: Vec<_> = (1..=3).map(|i| create_rgb_image(16 * i, 16)).collect();
+    let exif = b"II*\0\x08\0\0\0\0\0";
+    let data = HeifContextBuilder::new()
+        .image(CollectionImage::new(&frames[0]))
+        .image(CollectionImage {
+            settings: EncodeSettings {
+                quality: 50,
+                exif: Some(exif),
+                ..Default::default()
+            },
+            thumbnail_bbox_size: Some(16),
+            ..CollectionImage::new(&frames[1])
+        })
+        .image(CollectionImage {
+            settings: EncodeSettings {
+                lossless: true,
+                ..Default::default()
+            },
+            ..CollectionImage::new(&frames[2])
+        })
+        .primary(1)
+        .write_to_bytes()
+        .unwrap();
+
+    let ctx = HeifContext::read_from_bytes(&data).unwrap();
+    let images: Vec<_> = ctx.images().collect::<Result<_, _>>().unwrap();
+    let widths: Vec<_> = images.iter().map(|i| i.handle.width()).collect();
+    assert_eq!(widths, [16, 32, 48]);
+    let primary_flags: Vec<_> = images.iter().map(|i| i.is_primary).collect();
+    assert_eq!(primary_flags, [false, true, false]);
+
+    let second = ctx.image_by_id(images[1].id).unwrap();
+    assert!(second.is_primary());
+    let err = ctx.image_by_id(1000).err().unwrap();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Nonexisting_item_referenced
+    );
+
+    let primary = ctx.primary_image_handle().unwrap();
+    assert_eq!(primary.width(), 32);
+    assert_eq!(primary.thumbnails().count(), 1);
+    let kinds: Vec<_> = primary.metadata().map(|b| b.unwrap().kind()).collect();
+    assert_eq!(kinds, [MetadataKind::Exif]);
+}
+
+#[test]
+fn invalid_primary_index() {
+    let err = HeifContextBuilder::new().build().err().unwrap();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value
+    );
+
+    let image = create_rgb_image(16, 16);
+    let res = HeifContextBuilder::new()
+        .image(CollectionImage::new(&image))
+        .primary(1)
+        .build();
+    assert!(res.is_err());
+}
@@ -0,0 +1,26 @@
+use std::fs;
+
+use libheif_sys::{decode_file_to_rgba8, decode_to_rgb16, decode_to_rgba8};
+
+#[test]
+fn decode_file_into_rgba8() {
+    let decoded = decode_file_to_rgba8("data/test.heif").unwrap();
+    assert_eq!(decoded.width, 1652);
+    assert_eq!(decoded.height, 1791);
+    assert_eq!(decoded.bit_depth, 8);
+    assert_eq!(decoded.pixels.len(), 1652 * 1791 * 4);
+    assert!(decoded.pixels.chunks_exact(4).all(|p| p[3] == 255));
+}
+
+#[test]
+fn decode_bytes_into_rgba8_and_rgb16() {
+    let data = fs::read("data/test.heif").unwrap();
+    let rgba8 = decode_to_rgba8(&data).unwrap();
+    assert_eq!(rgba8.pixels.len(), 1652 * 1791 * 4);
+
+    let rgb16 = decode_to_rgb16(&data).unwrap();
+    assert_eq!((rgb16.width, rgb16.height), (1652, 1791));
+    assert_eq!(rgb16.pixels.len(), 1652 * 1791 * 3);
+
+    assert!(decode_to_rgba8(&data[..100]).is_err());
+}
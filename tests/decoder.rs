@@ -0,0 +1,42 @@
+use libheif_sys as lh;
+use libheif_sys::{
+    Chroma, ColorSpace, CompressionFormat, DecoderChoice, DecodingOptions, HeifContext,
+};
+
+#[test]
+fn choose_decoder() {
+    let ids = DecoderChoice::available_ids(CompressionFormat::Hevc);
+    assert!(!ids.is_empty());
+    let choice = DecoderChoice::new(CompressionFormat::Hevc, &ids[0]).unwrap();
+    assert_eq!(choice.format(), CompressionFormat::Hevc);
+    assert_eq!(choice.id(), ids[0]);
+
+    let err = DecoderChoice::new(CompressionFormat::Hevc, "unknown").unwrap_err();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value
+    );
+    let err = DecoderChoice::new(CompressionFormat::Mask, "unknown").unwrap_err();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Unsupported_codec
+    );
+}
+
+#[test]
+fn decode_with_every_decoder() {
+    let ctx = HeifContext::read_from_file("./data/test.heif").unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+    for id in DecoderChoice::available_ids(CompressionFormat::Av1) {
+        let choice = DecoderChoice::new(CompressionFormat::Av1, &id).unwrap();
+        let options = DecodingOptions::new()
+            .unwrap()
+            .strict_decoding(true)
+            .decoder(choice);
+        let image = handle
+            .decode_with_options(ColorSpace::Rgb, Chroma::InterleavedRgb, &options)
+            .unwrap();
+        assert_eq!(image.width(), handle.width());
+        assert_eq!(image.height(), handle.height());
+    }
+}
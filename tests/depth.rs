@@ -0,0 +1,9 @@
+use libheif_sys::HeifContext;
+
+#[test]
+fn image_without_depth() {
+    let ctx = HeifContext::read_from_file("./data/test.heif").unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+    assert!(!handle.has_depth_image());
+    assert_eq!(handle.depth_images().count(), 0);
+}
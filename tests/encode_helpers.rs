@@ -0,0 +1,34 @@
+use libheif_sys as lh;
+use libheif_sys::{encode_rgb_to_avif, encode_rgba_to_heic, EncodeSettings};
+
+fn main_brand(data: &[u8]) -> lh::heif_brand {
+    unsafe { lh::heif_main_brand(data.as_ptr(), data.len() as _) }
+}
+
+#[test]
+fn encode_rgba_pixels_to_heic() {
+    let (width, height) = (32, 24);
+    let pixels: Vec<u8> = (0..width * height * 4).map(|i| i as u8).collect();
+    let exif = b"II*\0\x08\0\0\0\0\0";
+    let settings = EncodeSettings {
+        quality: 50,
+        exif: Some(exif),
+        ..Default::default()
+    };
+    let data = encode_rgba_to_heic(&pixels, width, height, &settings).unwrap();
+    assert_eq!(main_brand(&data), lh::heif_brand_heif_heic);
+}
+
+#[test]
+fn encode_rgb_pixels_to_avif() {
+    let (width, height) = (16, 16);
+    let pixels = vec![200u8; (width * height * 3) as usize];
+    let data = encode_rgb_to_avif(&pixels, width, height, &EncodeSettings::default()).unwrap();
+    assert_eq!(main_brand(&data), lh::heif_brand_heif_avif);
+
+    let err = encode_rgb_to_avif(&pixels[1..], width, height, &Default::default()).unwrap_err();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value
+    );
+}
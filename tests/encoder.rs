@@ -0,0 +1,99 @@
+use libheif_sys as lh;
+use libheif_sys::{
+    encoder_descriptors, CompressionFormat, EncoderParameter, EncoderParameterKind, HeifContext,
+    ParamValue,
+};
+
+#[test]
+fn get_encoder_for_format() {
+    let ctx = HeifContext::new().unwrap();
+    let mut encoder = ctx.encoder_for_format(CompressionFormat::Hevc).unwrap();
+    assert!(!encoder.name().is_empty());
+
+    encoder.set_quality(75).unwrap();
+    encoder.set_lossless(false).unwrap();
+    encoder.set_logging_level(0).unwrap();
+
+    let err = encoder.set_quality(101).unwrap_err();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value
+    );
+}
+
+#[test]
+fn get_encoder_by_descriptor() {
+    let descriptors = encoder_descriptors(Some(CompressionFormat::Av1), None).unwrap();
+    assert!(!descriptors.is_empty());
+    let descriptor = descriptors[0];
+    assert_eq!(
+        descriptor.compression_format(),
+        Some(CompressionFormat::Av1)
+    );
+    assert!(!descriptor.id_name().is_empty());
+
+    let ctx = HeifContext::new().unwrap();
+    let mut encoder = ctx.encoder(&descriptor).unwrap();
+    encoder.set_lossless(true).unwrap();
+}
+
+#[test]
+fn encoder_parameters() {
+    let ctx = HeifContext::new().unwrap();
+    let mut encoder = ctx.encoder_for_format(CompressionFormat::Hevc).unwrap();
+    let params: Vec<EncoderParameter> = encoder.parameters().map(|p| p.unwrap()).collect();
+    assert!(!params.is_empty());
+
+    let quality = encoder.parameter("quality").unwrap().unwrap();
+    assert_eq!(
+        quality.kind,
+        EncoderParameterKind::Integer {
+            min: Some(0),
+            max: Some(100),
+            valid_values: vec![]
+        }
+    );
+    encoder.set("quality", ParamValue::Integer(42)).unwrap();
+    assert_eq!(encoder.get("quality").unwrap(), ParamValue::Integer(42));
+
+    let err = encoder
+        .set("quality", ParamValue::Integer(200))
+        .unwrap_err();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value
+    );
+    let err = encoder
+        .set("quality", ParamValue::Boolean(true))
+        .unwrap_err();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value
+    );
+    let err = encoder.set("unknown", ParamValue::Integer(1)).unwrap_err();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Unsupported_parameter
+    );
+}
+
+#[test]
+fn string_encoder_parameter() {
+    let ctx = HeifContext::new().unwrap();
+    let mut encoder = ctx.encoder_for_format(CompressionFormat::Hevc).unwrap();
+    let param = encoder
+        .parameters()
+        .map(|p| p.unwrap())
+        .find(|p| {
+            matches!(&p.kind, EncoderParameterKind::String { valid_values } if !valid_values.is_empty())
+        })
+        .unwrap();
+    let value = match &param.kind {
+        EncoderParameterKind::String { valid_values } => valid_values.last().unwrap().clone(),
+        _ => unreachable!(),
+    };
+    encoder
+        .set(&param.name, ParamValue::String(value.clone()))
+        .unwrap();
+    assert_eq!(encoder.get(&param.name).unwrap(), ParamValue::String(value));
+}
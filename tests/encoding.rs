@@ -0,0 +1,97 @@
+mod common;
+
+use libheif_sys as lh;
+use libheif_sys::{
+    Channel, Chroma, ColorPrimaries, ColorProfile, ColorSpace, CompressionFormat, DecodingOptions,
+    EncodingOptions, HeifContext, MatrixCoefficients, NclxProfile, Orientation,
+    TransferCharacteristics,
+};
+
+use common::{create_rgb_image, write_and_read};
+
+#[test]
+fn encode_image_with_orientation() {
+    let image = create_rgb_image(64, 32);
+    let plane = image.plane(Channel::Interleaved).unwrap();
+    assert_eq!(plane.width, 64);
+    assert_eq!(plane.height, 32);
+    assert_eq!(plane.storage_bits_per_pixel, 24);
+
+    let mut ctx = HeifContext::new().unwrap();
+    let mut encoder = ctx.encoder_for_format(CompressionFormat::Hevc).unwrap();
+    encoder.set_quality(90).unwrap();
+    let options = EncodingOptions::new()
+        .unwrap()
+        .save_alpha_channel(false)
+        .orientation(Orientation::Rotate90Cw);
+    let handle = ctx.encode_image(&image, &mut encoder, &options).unwrap();
+    assert!(handle.is_primary());
+
+    // Reader applies `irot` transformation.
+    let ctx = write_and_read(&ctx);
+    let handle = ctx.primary_image_handle().unwrap();
+    assert_eq!((handle.width(), handle.height()), (32, 64));
+    let image = handle
+        .decode(ColorSpace::Rgb, Chroma::InterleavedRgb)
+        .unwrap();
+    assert_eq!((image.width(), image.height()), (32, 64));
+    let options = DecodingOptions::new().unwrap().ignore_transformations(true);
+    let image = handle
+        .decode_with_options(ColorSpace::Rgb, Chroma::InterleavedRgb, &options)
+        .unwrap();
+    assert_eq!((image.width(), image.height()), (64, 32));
+}
+
+#[test]
+fn encode_image_with_output_nclx_profile() {
+    let image = create_rgb_image(32, 32);
+    let mut ctx = HeifContext::new().unwrap();
+    let mut encoder = ctx.encoder_for_format(CompressionFormat::Hevc).unwrap();
+    let profile = NclxProfile::new(
+        ColorPrimaries::Bt2020,
+        TransferCharacteristics::Bt2100Pq,
+        MatrixCoefficients::Bt2020NonConstantLuminance,
+        false,
+    );
+    let options = EncodingOptions::new()
+        .unwrap()
+        .output_nclx_profile(profile)
+        .macos_compatibility_workaround_no_nclx_profile(false);
+    let handle = ctx.encode_image(&image, &mut encoder, &options).unwrap();
+    assert!(handle.is_primary());
+
+    let ctx = write_and_read(&ctx);
+    let handle = ctx.primary_image_handle().unwrap();
+    let written_profile = handle.color_profile().unwrap();
+    // libheif before 1.16 writes NCLX profile of the input image
+    // instead of the output one.
+    if unsafe { lh::heif_get_version_number() } >= 0x01_10_00_00 {
+        assert_eq!(written_profile, Some(ColorProfile::Nclx(profile)));
+    } else {
+        assert!(matches!(written_profile, Some(ColorProfile::Nclx(_))));
+    }
+}
+
+#[test]
+fn invalid_output_nclx_profile() {
+    let image = create_rgb_image(32, 32);
+    let mut ctx = HeifContext::new().unwrap();
+    let mut encoder = ctx.encoder_for_format(CompressionFormat::Hevc).unwrap();
+    // Value 3 is reserved in ITU-T H.273.
+    let profile = NclxProfile::new(
+        ColorPrimaries::Unknown(3),
+        TransferCharacteristics::Bt2100Pq,
+        MatrixCoefficients::Bt2020NonConstantLuminance,
+        false,
+    );
+    let options = EncodingOptions::new().unwrap().output_nclx_profile(profile);
+
+    let err = ctx
+        .encode_image(&image, &mut encoder, &options)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Unknown_NCLX_color_primaries
+    );
+}
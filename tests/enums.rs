@@ -0,0 +1,53 @@
+use std::convert::TryFrom;
+
+use libheif_sys as lh;
+use libheif_sys::enums::{DepthRepresentationType, FileTypeResult, SuberrorCode};
+use libheif_sys::{Chroma, CompressionFormat};
+
+#[test]
+fn convert_enums_into_c_values() {
+    assert_eq!(
+        lh::heif_chroma::from(Chroma::C444),
+        lh::heif_chroma_heif_chroma_444
+    );
+    assert_eq!(
+        lh::heif_compression_format::from(CompressionFormat::Jpeg2000),
+        lh::heif_compression_format_heif_compression_JPEG2000
+    );
+    assert_eq!(
+        lh::heif_suberror_code::from(SuberrorCode::SecurityLimitExceeded),
+        lh::heif_suberror_code_heif_suberror_Security_limit_exceeded
+    );
+}
+
+#[test]
+fn convert_c_values_into_enums() {
+    assert_eq!(
+        Chroma::try_from(lh::heif_chroma_heif_chroma_interleaved_RRGGBB_BE),
+        Ok(Chroma::InterleavedRrggbbBe)
+    );
+    assert_eq!(
+        FileTypeResult::try_from(lh::heif_filetype_result_heif_filetype_yes_supported),
+        Ok(FileTypeResult::YesSupported)
+    );
+    assert_eq!(
+        DepthRepresentationType::try_from(
+            lh::heif_depth_representation_type_heif_depth_representation_type_uniform_Z
+        ),
+        Ok(DepthRepresentationType::UniformZ)
+    );
+    assert_eq!(Chroma::try_from(12345), Err(12345));
+}
+
+#[test]
+fn all_variants_of_enum() {
+    assert_eq!(
+        CompressionFormat::ALL.first(),
+        Some(&CompressionFormat::Undefined)
+    );
+    assert!(CompressionFormat::ALL.contains(&CompressionFormat::Mask));
+    for &format in CompressionFormat::ALL {
+        let c_value = lh::heif_compression_format::from(format);
+        assert_eq!(CompressionFormat::try_from(c_value), Ok(format));
+    }
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use libheif_sys::{probe, DisplayGeometry, HeifContext, PixelAspectRatio, Size};
+
+use common::{create_rgb_image, encode_primary, iso_box};
+
+fn size(width: u32, height: u32) -> Size {
+    Size { width, height }
+}
+
+#[test]
+fn apply_pixel_aspect_ratio() {
+    let square = PixelAspectRatio::default();
+    assert!(square.is_square());
+    assert_eq!(square.apply(size(40, 30)), size(40, 30));
+
+    let wide = PixelAspectRatio {
+        h_spacing: 4,
+        v_spacing: 3,
+    };
+    assert_eq!(wide.apply(size(30, 30)), size(40, 30));
+
+    let tall = PixelAspectRatio {
+        h_spacing: 1,
+        v_spacing: 2,
+    };
+    assert_eq!(tall.apply(size(30, 15)), size(30, 30));
+}
+
+#[test]
+fn display_geometry_of_anamorphic_image() {
+    let mut image = create_rgb_image(40, 20);
+    let ratio = PixelAspectRatio {
+        h_spacing: 2,
+        v_spacing: 1,
+    };
+    image.set_pixel_aspect_ratio(ratio);
+    assert_eq!(image.pixel_aspect_ratio(), ratio);
+
+    let mut ctx = HeifContext::new().unwrap();
+    encode_primary(&mut ctx, &image);
+    let info = probe(&ctx.write_to_bytes().unwrap()).unwrap();
+    let primary_item = info
+        .items
+        .iter()
+        .find(|item| Some(item.id) == info.primary_item_id)
+        .unwrap();
+    let geometry = primary_item.geometry.unwrap();
+    assert_eq!(geometry.ispe, size(40, 20));
+    assert_eq!(geometry.cropped, size(40, 20));
+    assert_eq!(geometry.rotation, 0);
+    assert_eq!(geometry.pixel_aspect_ratio, ratio);
+    assert_eq!(geometry.display, size(80, 20));
+}
+
+fn u32s(values: &[u32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_be_bytes()).collect()
+}
+
+/// Geometry of the only item of a file with the given properties.
+fn geometry_of_properties(properties: &[Vec<u8>]) -> DisplayGeometry {
+    let ipco = iso_box(b"ipco", &properties.concat());
+    let mut ipma_content = vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 1, properties.len() as u8];
+    ipma_content.extend(1..=properties.len() as u8);
+    let ipma = iso_box(b"ipma", &ipma_content);
+    let iprp = iso_box(b"iprp", &[ipco, ipma].concat());
+    let infe = iso_box(b"infe", &[2, 0, 0, 0, 0, 1, 0, 0, b'h', b'v', b'c', b'1']);
+    let iinf = iso_box(b"iinf", &[&[0, 0, 0, 0, 0, 1][..], &infe].concat());
+    let pitm = iso_box(b"pitm", &[0, 0, 0, 0, 0, 1]);
+    let meta = iso_box(b"meta", &[&[0, 0, 0, 0][..], &pitm, &iinf, &iprp].concat());
+    let ftyp = iso_box(b"ftyp", b"heic\0\0\0\0mif1");
+    let info = probe(&[ftyp, meta].concat()).unwrap();
+    info.items[0].geometry.unwrap()
+}
+
+#[test]
+fn display_geometry_of_rotated_anamorphic_image() {
+    let geometry = geometry_of_properties(&[
+        iso_box(b"ispe", &u32s(&[0, 40, 20])),
+        iso_box(b"pasp", &u32s(&[2, 1])),
+        iso_box(b"irot", &[3]),
+    ]);
+    assert_eq!(geometry.ispe, size(40, 20));
+    assert_eq!(geometry.cropped, size(20, 40));
+    assert_eq!(geometry.rotation, 270);
+    // Pixels are stretched horizontally before rotation.
+    assert_eq!(geometry.display, size(20, 80));
+}
+
+#[test]
+fn display_geometry_of_cropped_and_rotated_image() {
+    let geometry = geometry_of_properties(&[
+        iso_box(b"ispe", &u32s(&[0, 4000, 3000])),
+        iso_box(b"pasp", &u32s(&[2, 1])),
+        iso_box(b"clap", &u32s(&[2000, 1, 1000, 1, 0, 1, 0, 1])),
+        iso_box(b"irot", &[1]),
+    ]);
+    assert_eq!(geometry.ispe, size(4000, 3000));
+    assert_eq!(geometry.cropped, size(1000, 2000));
+    assert_eq!(geometry.rotation, 90);
+    assert_eq!(geometry.display, size(1000, 4000));
+}
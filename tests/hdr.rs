@@ -0,0 +1,75 @@
+mod common;
+
+use libheif_sys::{Chroma, Chromaticity, ContentLightLevel, HdrMetadata, Image, MasteringDisplay};
+
+fn create_image() -> Image {
+    common::create_image(16, 16, Chroma::InterleavedRrggbbLe, 10)
+}
+
+fn xy(x: f32, y: f32) -> Chromaticity {
+    Chromaticity { x, y }
+}
+
+/// Display P3 mastering display with D65 white point.
+fn mastering_display() -> MasteringDisplay {
+    MasteringDisplay {
+        display_primaries: [xy(0.265, 0.69), xy(0.15, 0.06), xy(0.68, 0.32)],
+        white_point: xy(0.3127, 0.329),
+        max_luminance: 1000.0,
+        min_luminance: 0.0001,
+    }
+}
+
+#[test]
+fn set_and_get_hdr_metadata() {
+    let mut image = create_image();
+    assert_eq!(image.hdr_metadata().unwrap(), HdrMetadata::default());
+
+    let clli = ContentLightLevel {
+        max_content_light_level: 1000,
+        max_pic_average_light_level: 400,
+    };
+    let metadata = HdrMetadata {
+        clli: Some(clli),
+        mdcv: Some(mastering_display()),
+    };
+    image.set_hdr_metadata(&metadata).unwrap();
+
+    let res = image.hdr_metadata().unwrap();
+    assert_eq!(res.clli, Some(clli));
+    let mdcv = res.mdcv.unwrap();
+    let expected = mastering_display();
+    for (p, e) in mdcv
+        .display_primaries
+        .iter()
+        .zip(&expected.display_primaries)
+    {
+        assert!((p.x - e.x).abs() < 1e-5);
+        assert!((p.y - e.y).abs() < 1e-5);
+    }
+    assert!((mdcv.white_point.x - 0.3127).abs() < 1e-5);
+    assert!((mdcv.max_luminance - 1000.0).abs() < 1e-6);
+    assert!((mdcv.min_luminance - 0.0001).abs() < 1e-9);
+}
+
+#[test]
+fn invalid_mastering_display_values() {
+    let mut image = create_image();
+    let mut mdcv = mastering_display();
+    mdcv.white_point.x = 1.5;
+    let metadata = HdrMetadata {
+        clli: Some(ContentLightLevel::default()),
+        mdcv: Some(mdcv),
+    };
+    assert!(image.set_hdr_metadata(&metadata).is_err());
+    // Nothing is changed in case of error.
+    assert_eq!(image.hdr_metadata().unwrap(), HdrMetadata::default());
+
+    mdcv.white_point.x = 0.3;
+    mdcv.max_luminance = -1.0;
+    let metadata = HdrMetadata {
+        clli: None,
+        mdcv: Some(mdcv),
+    };
+    assert!(image.set_hdr_metadata(&metadata).is_err());
+}
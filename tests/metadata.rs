@@ -0,0 +1,135 @@
+mod common;
+
+use libheif_sys as lh;
+use libheif_sys::{
+    encode_rgba_to_heic, EncodeSettings, HeifContext, MetadataCompression, MetadataKind,
+};
+
+use common::write_and_read;
+
+const EXIF: &[u8] = b"II*\0\x08\0\0\0\0\0";
+const XMP: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"></x:xmpmeta>"#;
+
+fn encode_with_metadata() -> Vec<u8> {
+    let pixels = vec![100u8; 16 * 16 * 4];
+    let settings = EncodeSettings {
+        exif: Some(EXIF),
+        xmp: Some(XMP),
+        ..Default::default()
+    };
+    encode_rgba_to_heic(&pixels, 16, 16, &settings).unwrap()
+}
+
+#[test]
+fn read_metadata_blocks() {
+    let ctx = HeifContext::read_from_bytes(&encode_with_metadata()).unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+
+    let blocks: Vec<_> = handle.metadata().collect::<Result<_, _>>().unwrap();
+    assert_eq!(blocks.len(), 2);
+
+    let exif = blocks
+        .iter()
+        .find(|b| b.kind() == MetadataKind::Exif)
+        .unwrap();
+    assert_eq!(exif.item_type, "Exif");
+    assert_eq!(exif.uri_type, None);
+    assert_eq!(&exif.data[4..], EXIF);
+
+    let xmp = blocks
+        .iter()
+        .find(|b| b.kind() == MetadataKind::Xmp)
+        .unwrap();
+    assert_eq!(xmp.item_type, "mime");
+    assert_eq!(xmp.content_type, "application/rdf+xml");
+    assert_eq!(xmp.data, XMP.as_bytes());
+}
+
+#[test]
+fn filter_metadata_blocks_by_type() {
+    let ctx = HeifContext::read_from_bytes(&encode_with_metadata()).unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+
+    let kinds: Vec<_> = handle
+        .metadata_of_type("Exif")
+        .unwrap()
+        .map(|b| b.unwrap().kind())
+        .collect();
+    assert_eq!(kinds, [MetadataKind::Exif]);
+    assert_eq!(handle.metadata_of_type("iptc").unwrap().count(), 0);
+}
+
+#[test]
+fn add_exif_and_read_tiff_bytes() {
+    let pixels = vec![100u8; 16 * 16 * 4];
+    let data = encode_rgba_to_heic(&pixels, 16, 16, &Default::default()).unwrap();
+    let mut ctx = HeifContext::read_from_bytes(&data).unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+
+    let mut app1 = b"Exif\0\0".to_vec();
+    app1.extend_from_slice(EXIF);
+    ctx.add_exif(&handle, &app1).unwrap();
+    let err = ctx.add_exif(&handle, b"not a TIFF").unwrap_err();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value
+    );
+
+    let ctx = write_and_read(&ctx);
+    let handle = ctx.primary_image_handle().unwrap();
+    let mut blocks = handle.metadata_of_type("Exif").unwrap();
+    let exif = blocks.next().unwrap().unwrap();
+    assert_eq!(exif.exif_tiff_bytes().unwrap(), EXIF);
+    assert!(blocks.next().is_none());
+}
+
+#[test]
+fn exif_tiff_bytes_validates_offset() {
+    let ctx = HeifContext::read_from_bytes(&encode_with_metadata()).unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+    let mut exif = handle
+        .metadata_of_type("Exif")
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(exif.exif_tiff_bytes().unwrap(), EXIF);
+
+    exif.data[3] = 100;
+    let err = exif.exif_tiff_bytes().unwrap_err();
+    assert_eq!(err.code, lh::heif_error_code_heif_error_Invalid_input);
+
+    let xmp = handle
+        .metadata_of_type("mime")
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert!(xmp.exif_tiff_bytes().is_err());
+}
+
+#[test]
+fn add_xmp_and_generic_metadata() {
+    let pixels = vec![100u8; 16 * 16 * 4];
+    let data = encode_rgba_to_heic(&pixels, 16, 16, &Default::default()).unwrap();
+    let mut ctx = HeifContext::read_from_bytes(&data).unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+
+    ctx.add_xmp(&handle, XMP, MetadataCompression::Off).unwrap();
+    ctx.add_generic_metadata(&handle, "iptc", None, b"\x1c\x02\x00")
+        .unwrap();
+    ctx.add_generic_metadata(&handle, "mime", Some("application/c2pa"), b"c2pa")
+        .unwrap();
+
+    let ctx = write_and_read(&ctx);
+    let handle = ctx.primary_image_handle().unwrap();
+    let blocks: Vec<_> = handle.metadata().collect::<Result<_, _>>().unwrap();
+    let kinds: Vec<_> = blocks.iter().map(|b| b.kind()).collect();
+    assert_eq!(
+        kinds,
+        [MetadataKind::Xmp, MetadataKind::Iptc, MetadataKind::Other]
+    );
+    assert_eq!(blocks[0].data, XMP.as_bytes());
+    assert_eq!(blocks[1].data, b"\x1c\x02\x00");
+    assert_eq!(blocks[2].content_type, "application/c2pa");
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use libheif_sys as lh;
+use libheif_sys::{
+    transfer_color_profile, transfer_metadata, ColorProfile, HeifContext, ImageHandle,
+    MetadataCompression, MetadataKind, MetadataPolicy,
+};
+
+use common::{create_rgb_image, encode_primary, write_and_read};
+
+const ICC: &[u8] = b"fake ICC profile";
+const XMP_WITH_GPS: &str = r#"<rdf:Description xmp:Rating="5" exif:GPSLatitude="50,1.5N">
+<exif:GPSLongitude>14,25.5E</exif:GPSLongitude><exif:GPSAltitude/>
+</rdf:Description>"#;
+const XMP_WITHOUT_GPS: &str = r#"<rdf:Description xmp:Rating="5">
+
+</rdf:Description>"#;
+
+/// Little-endian TIFF with Orientation tag and GPS IFD with GPSLatitude tag.
+fn tiff_with_gps() -> Vec<u8> {
+    let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+    // IFD0
+    tiff.extend_from_slice(&[2, 0]);
+    tiff.extend_from_slice(&[0x12, 0x01, 3, 0, 1, 0, 0, 0, 1, 0, 0, 0]);
+    tiff.extend_from_slice(&[0x25, 0x88, 4, 0, 1, 0, 0, 0, 38, 0, 0, 0]);
+    tiff.extend_from_slice(&[0, 0, 0, 0]);
+    // GPS IFD
+    tiff.extend_from_slice(&[1, 0]);
+    tiff.extend_from_slice(&[0x02, 0x00, 5, 0, 3, 0, 0, 0, 56, 0, 0, 0]);
+    tiff.extend_from_slice(&[0, 0, 0, 0]);
+    // GPSLatitude values
+    tiff.extend_from_slice(&[0x11; 24]);
+    assert_eq!(tiff.len(), 80);
+    tiff
+}
+
+/// Source file with ICC profile, Exif, XMP and IPTC metadata.
+fn source_file() -> Vec<u8> {
+    let mut image = create_rgb_image(16, 16);
+    image
+        .set_color_profile(ColorProfile::Icc {
+            restricted: false,
+            data: ICC.to_vec(),
+        })
+        .unwrap();
+    let mut ctx = HeifContext::new().unwrap();
+    let handle = encode_primary(&mut ctx, &image);
+    ctx.add_exif(&handle, &tiff_with_gps()).unwrap();
+    ctx.add_xmp(&handle, XMP_WITH_GPS, MetadataCompression::Off)
+        .unwrap();
+    ctx.add_generic_metadata(&handle, "iptc", None, b"\x1c\x02\x00")
+        .unwrap();
+    ctx.write_to_bytes().unwrap()
+}
+
+fn transcode(policy: MetadataPolicy) -> HeifContext {
+    let src_ctx = HeifContext::read_from_bytes(&source_file()).unwrap();
+    let src = src_ctx.primary_image_handle().unwrap();
+
+    let mut image = create_rgb_image(16, 16);
+    transfer_color_profile(&src, &mut image, policy).unwrap();
+    let mut ctx = HeifContext::new().unwrap();
+    let handle = encode_primary(&mut ctx, &image);
+    if policy != MetadataPolicy::OnlyIcc {
+        transfer_metadata(&src, &mut ctx, &handle, policy).unwrap();
+    }
+    write_and_read(&ctx)
+}
+
+fn icc_profile(handle: &ImageHandle) -> Option<Vec<u8>> {
+    match handle.color_profile().unwrap() {
+        Some(ColorProfile::Icc { data, .. }) => Some(data),
+        _ => None,
+    }
+}
+
+fn kinds(handle: &ImageHandle) -> Vec<MetadataKind> {
+    handle.metadata().map(|b| b.unwrap().kind()).collect()
+}
+
+#[test]
+fn transfer_all_metadata() {
+    let ctx = transcode(MetadataPolicy::All);
+    let handle = ctx.primary_image_handle().unwrap();
+    assert_eq!(icc_profile(&handle).as_deref(), Some(ICC));
+    assert_eq!(
+        kinds(&handle),
+        [MetadataKind::Exif, MetadataKind::Xmp, MetadataKind::Iptc]
+    );
+    let exif = handle
+        .metadata_of_type("Exif")
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(exif.exif_tiff_bytes().unwrap(), tiff_with_gps().as_slice());
+}
+
+#[test]
+fn transfer_metadata_without_gps() {
+    let ctx = transcode(MetadataPolicy::StripGps);
+    let handle = ctx.primary_image_handle().unwrap();
+    assert_eq!(icc_profile(&handle).as_deref(), Some(ICC));
+    assert_eq!(
+        kinds(&handle),
+        [MetadataKind::Exif, MetadataKind::Xmp, MetadataKind::Iptc]
+    );
+
+    let xmp = handle
+        .metadata()
+        .map(|b| b.unwrap())
+        .find(|b| b.kind() == MetadataKind::Xmp)
+        .unwrap();
+    assert_eq!(xmp.data, XMP_WITHOUT_GPS.as_bytes());
+
+    let exif = handle
+        .metadata_of_type("Exif")
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    let tiff = exif.exif_tiff_bytes().unwrap();
+    assert_eq!(tiff.len(), 80);
+    // IFD0 contains only Orientation tag and has no next IFD.
+    assert_eq!(&tiff[8..10], &[1, 0]);
+    assert_eq!(&tiff[10..12], &[0x12, 0x01]);
+    assert_eq!(&tiff[22..26], &[0, 0, 0, 0]);
+    assert!(tiff[26..].iter().all(|&b| b == 0));
+}
+
+#[test]
+fn transfer_only_icc_profile() {
+    let ctx = transcode(MetadataPolicy::OnlyIcc);
+    let handle = ctx.primary_image_handle().unwrap();
+    assert_eq!(icc_profile(&handle).as_deref(), Some(ICC));
+    assert!(kinds(&handle).is_empty());
+
+    let err = transfer_metadata(
+        &handle,
+        &mut HeifContext::new().unwrap(),
+        &handle,
+        MetadataPolicy::OnlyIcc,
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.code, lh::heif_error_code_heif_error_Usage_error);
+
+    let ctx = transcode(MetadataPolicy::StripAll);
+    let handle = ctx.primary_image_handle().unwrap();
+    assert_eq!(icc_profile(&handle), None);
+    assert!(kinds(&handle).is_empty());
+}
@@ -0,0 +1,45 @@
+use std::env;
+use std::fs;
+
+use libheif_sys as lh;
+use libheif_sys::PluginRegistry;
+
+#[test]
+fn default_directories() {
+    let registry = PluginRegistry::new();
+    for dir in registry.default_directories() {
+        assert!(!dir.as_os_str().is_empty());
+    }
+}
+
+#[test]
+fn load_missing_plugin() {
+    let registry = PluginRegistry::new();
+    let err = registry
+        .load("/nonexistent/libheif-plugin.so")
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.code,
+        lh::heif_error_code_heif_error_Plugin_loading_error
+    );
+}
+
+#[test]
+fn load_directories() {
+    let registry = PluginRegistry::new();
+    let dir = env::temp_dir().join("libheif-sys-empty-plugins");
+    fs::create_dir_all(&dir).unwrap();
+    let plugins = registry.load_dir(&dir).unwrap();
+    assert!(plugins.is_empty());
+
+    let err = registry.load_dir(dir.join("nonexistent")).err().unwrap();
+    assert_eq!(
+        err.code,
+        lh::heif_error_code_heif_error_Plugin_loading_error
+    );
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Cannot_read_plugin_directory
+    );
+}
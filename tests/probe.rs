@@ -0,0 +1,123 @@
+mod common;
+
+use std::fs;
+
+use libheif_sys as lh;
+use libheif_sys::{probe, Brand, HeifContext, ProbeReference, Size};
+
+use common::iso_box;
+
+#[test]
+fn probe_test_file() {
+    let data = fs::read("./data/test.heif").unwrap();
+    let info = probe(&data).unwrap();
+
+    let main_brand = unsafe { lh::heif_read_main_brand(data.as_ptr(), data.len() as _) };
+    assert_eq!(info.main_brand, Brand::from(main_brand));
+    assert_eq!(info.main_brand, Brand::AVIF);
+    // Brands are duplicated in the test file.
+    let brands = [Brand::AVIF, Brand::MIF1, Brand::MIAF];
+    assert_eq!(info.compatible_brands, [brands, brands].concat());
+    assert_eq!(info.handler_type.as_deref(), Some("pict"));
+
+    let ctx = HeifContext::read_from_bytes(&data).unwrap();
+    let mut primary_id = 0;
+    let err = unsafe { lh::heif_context_get_primary_image_ID(ctx.as_ptr(), &mut primary_id) };
+    assert_eq!(err.code, lh::heif_error_code_heif_error_Ok);
+    assert_eq!(info.primary_item_id, Some(primary_id));
+
+    let handle = ctx.primary_image_handle().unwrap();
+    let (width, height) = unsafe {
+        (
+            lh::heif_image_handle_get_ispe_width(handle.as_ptr()),
+            lh::heif_image_handle_get_ispe_height(handle.as_ptr()),
+        )
+    };
+    assert_eq!(
+        info.primary_size,
+        Some(Size {
+            width: width as u32,
+            height: height as u32,
+        })
+    );
+    assert_eq!(
+        info.primary_size,
+        Some(Size {
+            width: 1652,
+            height: 1791,
+        })
+    );
+
+    let items: Vec<_> = info
+        .items
+        .iter()
+        .map(|item| (item.id, item.item_type.as_str(), item.geometry.is_some()))
+        .collect();
+    assert_eq!(
+        items,
+        [(1, "av01", true), (2, "Exif", false), (3, "av01", true)]
+    );
+
+    let reference = |reference_type: &str, from_item_id, to_item_id| ProbeReference {
+        reference_type: reference_type.into(),
+        from_item_id,
+        to_item_ids: vec![to_item_id],
+    };
+    assert_eq!(
+        info.references,
+        [reference("cdsc", 2, 1), reference("thmb", 3, 1)]
+    );
+}
+
+#[test]
+fn probe_truncated_data() {
+    let data = fs::read("./data/test.heif").unwrap();
+    // Only `ftyp` box and a part of `meta` box.
+    let info = probe(&data[..100]).unwrap();
+    assert_eq!(info.main_brand, Brand::AVIF);
+    assert_eq!(info.compatible_brands.len(), 6);
+    assert_eq!(info.handler_type, None);
+    assert_eq!(info.primary_item_id, None);
+    assert_eq!(info.primary_size, None);
+
+    for data in [&data[..20], &b"plain text file"[..], &[]] {
+        let err = probe(data).unwrap_err();
+        assert_eq!(err.code, lh::heif_error_code_heif_error_Invalid_input);
+        assert_eq!(
+            err.sub_code,
+            lh::heif_suberror_code_heif_suberror_No_ftyp_box
+        );
+    }
+}
+
+#[test]
+fn probe_invalid_box_size() {
+    let mut data = fs::read("./data/test.heif").unwrap();
+    // Size of `hdlr` box inside of `meta` box.
+    data[0x34..0x38].copy_from_slice(&4u32.to_be_bytes());
+    let err = probe(&data).unwrap_err();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_box_size
+    );
+}
+
+#[test]
+fn probe_old_infe_versions() {
+    // `infe` boxes of version 0 and 1 don't contain item type.
+    let infe_v0 = iso_box(b"infe", &[0, 0, 0, 0, 0, 1, 0, 0, 0]);
+    let infe_v1 = iso_box(b"infe", &[1, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0]);
+    let infe_v2 = iso_box(b"infe", &[2, 0, 0, 0, 0, 3, 0, 0, b'h', b'v', b'c', b'1']);
+    let iinf_content = [&[0, 0, 0, 0, 0, 3][..], &infe_v0, &infe_v1, &infe_v2].concat();
+    let iinf = iso_box(b"iinf", &iinf_content);
+    let meta = iso_box(b"meta", &[&[0, 0, 0, 0][..], &iinf].concat());
+    let ftyp = iso_box(b"ftyp", b"heic\0\0\0\0mif1");
+    let info = probe(&[ftyp, meta].concat()).unwrap();
+
+    let items: Vec<_> = info
+        .items
+        .iter()
+        .map(|item| (item.id, item.item_type.as_str()))
+        .collect();
+    assert_eq!(items, [(1, ""), (2, ""), (3, "hvc1")]);
+}
@@ -0,0 +1,102 @@
+use std::fs;
+
+use libheif_sys as lh;
+use libheif_sys::{HeifContext, HeifError, SecurityLimits};
+
+fn assert_limit_exceeded(err: HeifError) {
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Security_limit_exceeded,
+        "{}",
+        err
+    );
+}
+
+fn read(limits: SecurityLimits) -> Result<HeifContext, HeifError> {
+    HeifContext::read_from_file_with_limits("./data/test.heif", &limits)
+}
+
+#[test]
+fn untrusted_limits_accept_test_file() {
+    let ctx = read(SecurityLimits::untrusted()).unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+    assert_eq!(handle.width(), 1652);
+}
+
+#[test]
+fn exceeded_limits() {
+    // The test file contains 3 items, the thumbnail references the primary
+    // image, sizes of Exif metadata and ICC profile exceed 100 bytes.
+    let limits = [
+        SecurityLimits {
+            max_image_size: Some(1000),
+            ..Default::default()
+        },
+        SecurityLimits {
+            max_items: Some(2),
+            ..Default::default()
+        },
+        SecurityLimits {
+            max_iref_depth: Some(0),
+            ..Default::default()
+        },
+        SecurityLimits {
+            max_metadata_size: Some(100),
+            ..Default::default()
+        },
+        SecurityLimits {
+            max_icc_size: Some(100),
+            ..Default::default()
+        },
+    ];
+    for limits in limits.iter() {
+        assert_limit_exceeded(read(*limits).err().unwrap());
+    }
+
+    let limits = SecurityLimits {
+        max_items: Some(3),
+        max_iref_depth: Some(1),
+        ..Default::default()
+    };
+    assert!(read(limits).is_ok());
+}
+
+#[test]
+fn circular_references() {
+    let mut data = fs::read("./data/test.heif").unwrap();
+    // Change `cdsc` reference from 2 to 1 into reference from 1 to 3,
+    // while the thumbnail 3 references the item 1.
+    let pos = data.windows(4).position(|w| w == b"cdsc").unwrap() + 4;
+    data[pos..pos + 2].copy_from_slice(&1u16.to_be_bytes());
+    data[pos + 4..pos + 6].copy_from_slice(&3u16.to_be_bytes());
+
+    for max_depth in [16, usize::MAX] {
+        let limits = SecurityLimits {
+            max_iref_depth: Some(max_depth),
+            ..Default::default()
+        };
+        let err = HeifContext::read_from_bytes_with_limits(&data, &limits)
+            .err()
+            .unwrap();
+        assert_limit_exceeded(err);
+    }
+}
+
+#[test]
+fn file_errors() {
+    let limits = SecurityLimits::untrusted();
+    let err = HeifContext::read_from_file_with_limits("./data/not-exists.heif", &limits)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.code,
+        lh::heif_error_code_heif_error_Input_does_not_exist
+    );
+
+    // Directory exists but can't be read as a file.
+    let err = HeifContext::read_from_file_with_limits("./data", &limits)
+        .err()
+        .unwrap();
+    assert_eq!(err.code, lh::heif_error_code_heif_error_Invalid_input);
+    assert!(err.message.contains("./data"), "{}", err);
+}
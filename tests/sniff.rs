@@ -0,0 +1,43 @@
+use std::fs;
+
+use libheif_sys::{sniff, Brand, FileTypeResult};
+
+#[test]
+fn brand_fourcc_round_trip() {
+    assert_eq!(Brand::from_fourcc(*b"heic"), Brand::HEIC);
+    assert_eq!(Brand::AVIF.fourcc(), *b"avif");
+    let brand = Brand::from_fourcc(*b"abcd");
+    assert_eq!(Brand::from_fourcc(brand.fourcc()), brand);
+    assert_eq!(brand.to_string(), "abcd");
+    assert_eq!(format!("{:?}", Brand::MIF1), r#"Brand("mif1")"#);
+}
+
+#[test]
+fn sniff_avif_file() {
+    let data = fs::read("./data/test.heif").unwrap();
+    let res = sniff(&data[..64]);
+    assert_eq!(res.filetype, FileTypeResult::YesSupported);
+    assert_eq!(res.main_brand, Brand::AVIF);
+    assert!(res.compatible_brands.contains(&Brand::MIF1));
+    assert!(res.compatible_brands.contains(&Brand::MIAF));
+    assert_eq!(res.mime, Some("image/avif"));
+    assert!(!res.is_jpeg);
+}
+
+#[test]
+fn sniff_jpeg_and_unknown_data() {
+    let jpeg = [
+        0xff, 0xd8, 0xff, 0xe0, 0, 0x10, b'J', b'F', b'I', b'F', 0, 1,
+    ];
+    let res = sniff(&jpeg);
+    assert_eq!(res.filetype, FileTypeResult::No);
+    assert_eq!(res.main_brand, Brand::UNKNOWN);
+    assert!(res.compatible_brands.is_empty());
+    assert_eq!(res.mime, Some("image/jpeg"));
+    assert!(res.is_jpeg);
+
+    let res = sniff(b"plain text file");
+    assert_eq!(res.filetype, FileTypeResult::No);
+    assert_eq!(res.mime, None);
+    assert!(!res.is_jpeg);
+}
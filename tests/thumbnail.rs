@@ -0,0 +1,76 @@
+mod common;
+
+use libheif_sys as lh;
+use libheif_sys::{Chroma, ColorSpace, CompressionFormat, EncodingOptions, HeifContext};
+
+use common::create_rgb_image;
+
+fn encode_with_thumbnail(bbox_size: u32) -> Vec<u8> {
+    let mut ctx = HeifContext::new().unwrap();
+    let mut encoder = ctx.encoder_for_format(CompressionFormat::Hevc).unwrap();
+    let options = EncodingOptions::new().unwrap();
+    let image = create_rgb_image(128, 64);
+    let handle = ctx.encode_image(&image, &mut encoder, &options).unwrap();
+
+    assert!(ctx
+        .add_thumbnail(&handle, &image, bbox_size, &mut encoder, &options)
+        .unwrap()
+        .is_some());
+    // Master image already fits into the area.
+    assert!(ctx
+        .add_thumbnail(&handle, &image, 256, &mut encoder, &options)
+        .unwrap()
+        .is_none());
+    let err = ctx
+        .add_thumbnail(&handle, &image, 0, &mut encoder, &options)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value
+    );
+    ctx.write_to_bytes().unwrap()
+}
+
+#[test]
+fn read_thumbnails() {
+    let ctx = HeifContext::read_from_bytes(&encode_with_thumbnail(32)).unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+    let thumbnails: Vec<_> = handle.thumbnails().collect::<Result<_, _>>().unwrap();
+    assert_eq!(thumbnails.len(), 1);
+    assert_eq!(thumbnails[0].width(), 32);
+    assert_eq!(thumbnails[0].height(), 16);
+    assert!(!thumbnails[0].is_primary());
+}
+
+#[test]
+fn best_thumbnail() {
+    let ctx = HeifContext::read_from_bytes(&encode_with_thumbnail(32)).unwrap();
+    let handle = ctx.primary_image_handle().unwrap();
+
+    // Embedded thumbnail is used as is.
+    let image = handle
+        .best_thumbnail(32, ColorSpace::Rgb, Chroma::InterleavedRgb)
+        .unwrap();
+    assert_eq!((image.width(), image.height()), (32, 16));
+
+    // Embedded thumbnail is scaled down.
+    let image = handle
+        .best_thumbnail(16, ColorSpace::Rgb, Chroma::InterleavedRgb)
+        .unwrap();
+    assert_eq!((image.width(), image.height()), (16, 8));
+
+    // Embedded thumbnail is too small, master image is scaled down.
+    let image = handle
+        .best_thumbnail(100, ColorSpace::Rgb, Chroma::InterleavedRgb)
+        .unwrap();
+    assert_eq!((image.width(), image.height()), (100, 50));
+    let err = handle
+        .best_thumbnail(0, ColorSpace::Rgb, Chroma::InterleavedRgb)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.sub_code,
+        lh::heif_suberror_code_heif_suberror_Invalid_parameter_value
+    );
+}